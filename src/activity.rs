@@ -0,0 +1,142 @@
+use crate::display::print_warning;
+use crate::store::Store;
+use crate::todo::Todo;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// What happened to a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Added,
+    Completed,
+    Reopened,
+    Edited,
+    Snoozed,
+    Deferred,
+    Deleted,
+    Cleared,
+}
+
+impl Action {
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::Added => "added",
+            Action::Completed => "completed",
+            Action::Reopened => "reopened",
+            Action::Edited => "edited",
+            Action::Snoozed => "snoozed",
+            Action::Deferred => "deferred",
+            Action::Deleted => "deleted",
+            Action::Cleared => "cleared",
+        }
+    }
+}
+
+/// One line of the activity log, `~/.tsk/log.jsonl`. The task's text and
+/// project are copied so the entry still reads well once the task is gone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Activity {
+    pub at: DateTime<Local>,
+    pub action: Action,
+    pub id: u32,
+    pub uuid: String,
+    pub text: String,
+    pub project: Option<String>,
+}
+
+impl Activity {
+    pub fn new(action: Action, todo: &Todo) -> Self {
+        Self {
+            at: Local::now(),
+            action,
+            id: todo.id,
+            uuid: todo.uuid.clone(),
+            text: todo.text.clone(),
+            project: todo.project.clone(),
+        }
+    }
+}
+
+fn path() -> io::Result<PathBuf> {
+    Ok(Store::data_dir()?.join("log.jsonl"))
+}
+
+/// Log `action` for each task, warning rather than failing the command if
+/// the log can't be written.
+pub fn record(action: Action, todos: &[Todo]) {
+    if todos.is_empty() {
+        return;
+    }
+
+    let entries: Vec<Activity> = todos.iter().map(|t| Activity::new(action, t)).collect();
+    if let Err(e) = append(&entries) {
+        print_warning(&format!("Could not write the activity log: {}", e));
+    }
+}
+
+pub fn append(entries: &[Activity]) -> io::Result<()> {
+    let path = path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    for entry in entries {
+        let line = serde_json::to_string(entry).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        writeln!(file, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Every logged entry, oldest first.
+pub fn load() -> io::Result<Vec<Activity>> {
+    let path = path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    parse(&fs::read_to_string(&path)?)
+}
+
+/// Entries from the log's JSON lines, skipping blank ones. A malformed line
+/// fails with its line number.
+fn parse(content: &str) -> io::Result<Vec<Activity>> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", i + 1, e)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(action: Action, id: u32) -> String {
+        serde_json::to_string(&Activity::new(action, &Todo::new(id, "task".to_string()))).unwrap()
+    }
+
+    #[test]
+    fn reads_entries_back() {
+        let content = format!("{}\n\n{}\n", line(Action::Added, 1), line(Action::Completed, 1));
+        let entries = parse(&content).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].action, Action::Completed);
+    }
+
+    #[test]
+    fn malformed_line_names_its_number() {
+        let content = format!("{}\n{{\"at\": 1}}\n", line(Action::Added, 1));
+        let err = parse(&content).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().starts_with("line 2:"), "{}", err);
+    }
+}
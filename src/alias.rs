@@ -0,0 +1,133 @@
+use crate::cli::Cli;
+use clap::CommandFactory;
+use std::collections::HashMap;
+
+/// User-defined commands from the `[aliases]` config section. An alias
+/// stands for one command line, a macro for several run in turn.
+#[derive(Debug, Default)]
+pub struct Aliases {
+    /// Words of each command, by alias name
+    commands: HashMap<String, Vec<Vec<String>>>,
+}
+
+impl Aliases {
+    pub fn new(commands: HashMap<String, Vec<Vec<String>>>) -> Self {
+        Aliases { commands }
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.commands.keys()
+    }
+
+    /// Replace an alias in command position, after any global flags, with
+    /// its words. Returns one argument list per command to run: several for
+    /// a macro, each followed by the arguments given after its name.
+    pub fn expand(&self, args: &[String]) -> Result<Vec<Vec<String>>, String> {
+        self.expand_from(args, &mut Vec::new())
+    }
+
+    fn expand_from(&self, args: &[String], seen: &mut Vec<String>) -> Result<Vec<Vec<String>>, String> {
+        let Some(pos) = command_position(args) else {
+            return Ok(vec![args.to_vec()]);
+        };
+        let Some(steps) = self.commands.get(&args[pos]) else {
+            return Ok(vec![args.to_vec()]);
+        };
+
+        let name = &args[pos];
+        if seen.contains(name) {
+            return Err(format!("Alias \"{}\" refers to itself", name));
+        }
+        seen.push(name.clone());
+
+        let mut runs = Vec::new();
+        for step in steps {
+            let expanded: Vec<String> = args[..pos].iter().chain(step).chain(&args[pos + 1..]).cloned().collect();
+            runs.extend(self.expand_from(&expanded, &mut seen.clone())?);
+        }
+        Ok(runs)
+    }
+}
+
+/// True for the name or short alias of a built-in command.
+pub fn is_builtin(name: &str) -> bool {
+    name == "help"
+        || Cli::command()
+            .get_subcommands()
+            .any(|c| c.get_name() == name || c.get_all_aliases().any(|a| a == name))
+}
+
+/// Index of the subcommand word: the first argument that is neither a
+/// global flag nor a flag's value.
+fn command_position(args: &[String]) -> Option<usize> {
+    let command = Cli::command();
+    let takes_value = |flag: &str| {
+        command.get_arguments().any(|a| {
+            a.get_action().takes_values()
+                && (a.get_long().is_some_and(|l| flag.strip_prefix("--") == Some(l))
+                    || a.get_short().is_some_and(|s| flag == format!("-{}", s)))
+        })
+    };
+
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        if arg == "--" {
+            return None;
+        }
+        if !arg.starts_with('-') {
+            return Some(i);
+        }
+        i += if takes_value(arg) { 2 } else { 1 };
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_string).collect()
+    }
+
+    fn aliases(entries: &[(&str, &[&str])]) -> Aliases {
+        let commands = entries
+            .iter()
+            .map(|(name, steps)| (name.to_string(), steps.iter().map(|s| args(s)).collect()))
+            .collect();
+        Aliases::new(commands)
+    }
+
+    #[test]
+    fn expands_after_global_flags() {
+        let aliases = aliases(&[("standup", &["ls -P work --by time"])]);
+        assert_eq!(
+            aliases.expand(&args("--json standup -T ops")),
+            Ok(vec![args("--json ls -P work --by time -T ops")])
+        );
+        assert_eq!(aliases.expand(&args("ls standup")), Ok(vec![args("ls standup")]));
+    }
+
+    #[test]
+    fn self_reference_errors() {
+        let aliases = aliases(&[("mine", &["mine -P me"])]);
+        assert_eq!(aliases.expand(&args("mine")), Err("Alias \"mine\" refers to itself".to_string()));
+    }
+
+    #[test]
+    fn cycle_errors() {
+        let aliases = aliases(&[("ping", &["pong"]), ("pong", &["--json ping"])]);
+        assert!(aliases.expand(&args("ping")).is_err());
+        assert!(aliases.expand(&args("pong")).is_err());
+    }
+
+    #[test]
+    fn macro_runs_each_step() {
+        let aliases = aliases(&[("morning", &["overdue", "today", "standup"]), ("standup", &["ls -P work"])]);
+        assert_eq!(
+            aliases.expand(&args("morning --wrap")),
+            Ok(vec![args("overdue --wrap"), args("today --wrap"), args("ls -P work --wrap")])
+        );
+    }
+}
@@ -1,4 +1,11 @@
+use crate::activity::Action;
+use crate::display::{Column, Layout, OutputFormat};
+use crate::project::{ProjectColor, ProjectStatus};
+use crate::store::TaskRef;
+use crate::time::{parse_deadline, parse_zone};
+use chrono_tz::Tz;
 use clap::{Parser, Subcommand};
+use serde::Deserialize;
 
 #[derive(Parser)]
 #[command(name = "tsk")]
@@ -26,9 +33,38 @@ pub struct Cli {
     /// Filter by project
     #[arg(short = 'P', long = "project", global = true)]
     pub project: Option<String>,
+
+    /// Output JSON
+    #[arg(long, global = true, conflicts_with = "jsonl")]
+    pub json: bool,
+
+    /// Output JSON Lines (one object per line)
+    #[arg(long, global = true)]
+    pub jsonl: bool,
+
+    /// Columns to show, comma-separated (id, uuid, priority, text, due, created, age, project, tags, status, estimate)
+    #[arg(long, global = true, value_delimiter = ',', conflicts_with = "template")]
+    pub columns: Vec<Column>,
+
+    /// Row template, e.g. "{id} {text} ({due:relative})"
+    #[arg(long, global = true)]
+    pub template: Option<String>,
+
+    /// Wrap long task text instead of truncating it
+    #[arg(long, global = true)]
+    pub wrap: bool,
+
+    /// Show deadlines in this zone, e.g. "Europe/Berlin"
+    #[arg(long, global = true, value_name = "ZONE", value_parser = parse_zone_arg)]
+    pub tz: Option<Tz>,
+}
+
+fn parse_zone_arg(s: &str) -> Result<Tz, String> {
+    parse_zone(s).ok_or_else(|| format!("unknown time zone \"{}\"", s))
 }
 
-#[derive(Clone, Copy, clap::ValueEnum)]
+#[derive(Debug, Clone, Copy, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SortBy {
     Priority,
     Time,
@@ -50,6 +86,14 @@ pub enum Command {
         /// Deadline time
         #[arg(short, long)]
         t: Option<String>,
+
+        /// Estimated effort (e.g. 30m, 2h, 1h30m)
+        #[arg(short, long)]
+        estimate: Option<String>,
+
+        /// Remind before the deadline (15m, 1h, 2d) or at a time (can be repeated)
+        #[arg(short, long)]
+        remind: Vec<String>,
     },
 
     /// List all tasks (alias)
@@ -61,22 +105,44 @@ pub enum Command {
     /// Mark task(s) as done
     #[command(alias = "d")]
     Done {
-        /// Task ID(s) to complete
-        ids: Vec<u32>,
+        /// Task IDs (3, 1-5,9, UUID prefix), +tag or @project
+        ids: Vec<String>,
+
+        /// Select overdue tasks
+        #[arg(long)]
+        overdue: bool,
+
+        /// Skip the confirmation prompt for bulk changes
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Reopen completed task(s)
+    Reopen {
+        /// Task IDs (3, 1-5,9, UUID prefix)
+        ids: Vec<String>,
     },
 
     /// Delete task(s)
     #[command(alias = "rm")]
     Delete {
-        /// Task ID(s) to delete
-        ids: Vec<u32>,
+        /// Task IDs (3, 1-5,9, UUID prefix), +tag or @project
+        ids: Vec<String>,
+
+        /// Select overdue tasks
+        #[arg(long)]
+        overdue: bool,
+
+        /// Skip the confirmation prompt for bulk changes
+        #[arg(short, long)]
+        yes: bool,
     },
 
-    /// Edit a task
+    /// Edit task(s)
     #[command(alias = "e")]
     Edit {
-        /// Task ID to edit
-        id: u32,
+        /// Task IDs (3, 1-5,9, UUID prefix), +tag or @project
+        target: String,
 
         /// New task text
         text: Vec<String>,
@@ -100,11 +166,103 @@ pub enum Command {
         /// Clear project
         #[arg(long)]
         clear_project: bool,
+
+        /// New estimated effort (e.g. 30m, 2h, 1h30m)
+        #[arg(short, long)]
+        estimate: Option<String>,
+
+        /// Clear estimate
+        #[arg(long)]
+        clear_estimate: bool,
+
+        /// Add a reminder before the deadline (15m, 1h, 2d) or at a time (can be repeated)
+        #[arg(short, long)]
+        remind: Vec<String>,
+
+        /// Remove all reminders
+        #[arg(long)]
+        clear_reminders: bool,
+
+        /// Select overdue tasks
+        #[arg(long)]
+        overdue: bool,
+
+        /// Skip the confirmation prompt for bulk changes
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Push deadline(s) back by a duration from now
+    Snooze {
+        /// Task IDs (3, 1-5,9, UUID prefix), +tag or @project, then how long (2h, 30m, 1 day);
+        /// just the duration with --overdue
+        #[arg(required = true)]
+        args: Vec<String>,
+
+        /// Select overdue tasks
+        #[arg(long)]
+        overdue: bool,
+
+        /// Skip the confirmation prompt for bulk changes
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Move deadline(s) to a new time, or by +/- an offset from the current one (`-- -1d` to move earlier)
+    Defer {
+        /// Task IDs (3, 1-5,9, UUID prefix), +tag or @project, then the new time (monday, +2d);
+        /// just the time with --overdue
+        #[arg(required = true)]
+        args: Vec<String>,
+
+        /// Keep each task's time of day when deferring to a date
+        #[arg(long)]
+        keep_time: bool,
+
+        /// Select overdue tasks
+        #[arg(long)]
+        overdue: bool,
+
+        /// Skip the confirmation prompt for bulk changes
+        #[arg(short, long)]
+        yes: bool,
     },
 
     /// Clear all completed tasks
     Clear,
 
+    /// Watch the store and send reminders as they come due
+    Daemon {
+        /// Seconds between checks
+        #[arg(long, default_value_t = 30)]
+        interval: u64,
+
+        /// Check once and exit
+        #[arg(long)]
+        once: bool,
+    },
+
+    /// Reassign small IDs to open tasks
+    Renumber,
+
+    /// Show the change history of task(s), or recent activity across all tasks
+    Log {
+        /// Task IDs (3, 1-5,9, UUID prefix); omit for the activity feed
+        ids: Vec<String>,
+
+        /// Only activity from this time on, e.g. "monday" or "2026-03-01"
+        #[arg(long, conflicts_with = "ids")]
+        since: Option<String>,
+
+        /// Only activity up to this time; a date includes the whole day
+        #[arg(long, conflicts_with = "ids")]
+        until: Option<String>,
+
+        /// Only these actions (can be repeated)
+        #[arg(short, long, value_enum, conflicts_with = "ids")]
+        action: Vec<Action>,
+    },
+
     /// Undo last action
     #[command(alias = "u")]
     Undo,
@@ -121,16 +279,161 @@ pub enum Command {
     /// Show overdue tasks
     Overdue,
 
-    /// Show tasks in a project
-    #[command(name = "project")]
+    /// Show tasks in a project, or manage projects
+    #[command(name = "project", args_conflicts_with_subcommands = true)]
     Project {
+        #[command(subcommand)]
+        action: Option<ProjectAction>,
+
         /// Project name
-        name: String,
+        name: Option<String>,
     },
 
     /// List all projects
     #[command(name = "projects")]
     Projects,
+
+    /// Manage tags
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
+    },
+
+    /// Show or change settings
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// List all tags with task counts
+    Tags,
+
+    /// Interactive full-screen mode
+    Tui,
+
+    /// Interactive shell: run many commands against one loaded store
+    Shell,
+
+    /// Show how a date expression resolves
+    #[command(name = "parse-date")]
+    ParseDate {
+        /// Date expression, e.g. "first monday of next month 3pm"
+        #[arg(required = true)]
+        expr: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProjectAction {
+    /// Create a project record
+    New {
+        /// Project name
+        name: String,
+
+        /// What the project is about
+        #[arg(short, long)]
+        description: Option<String>,
+
+        /// Target completion date
+        #[arg(short, long)]
+        t: Option<String>,
+
+        /// Display color
+        #[arg(long)]
+        hue: Option<ProjectColor>,
+    },
+
+    /// Change a project's details
+    Edit {
+        /// Project name
+        name: String,
+
+        /// New description
+        #[arg(short, long)]
+        description: Option<String>,
+
+        /// New target completion date
+        #[arg(short, long)]
+        t: Option<String>,
+
+        /// New status
+        #[arg(short, long)]
+        status: Option<ProjectStatus>,
+
+        /// New display color
+        #[arg(long)]
+        hue: Option<ProjectColor>,
+
+        /// Clear description
+        #[arg(long)]
+        clear_description: bool,
+
+        /// Clear target date
+        #[arg(long)]
+        clear_time: bool,
+
+        /// Clear display color
+        #[arg(long)]
+        clear_hue: bool,
+    },
+
+    /// Mark a project as done
+    Close {
+        /// Project name
+        name: String,
+    },
+
+    /// Rename a project; renaming onto an existing project merges them
+    Rename {
+        /// Current project name
+        old: String,
+
+        /// New project name
+        new: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Show every setting, with defaults for those not set
+    List,
+
+    /// Show one setting, e.g. `dates.clock`
+    Get { key: String },
+
+    /// Change a setting in ~/.config/tsk/config.toml
+    Set {
+        /// Setting, e.g. `display.color`
+        key: String,
+
+        /// New value: a string, number, boolean or TOML array
+        value: String,
+
+        /// Write to the nearest .tsk.toml instead (created here if none)
+        #[arg(long)]
+        local: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TagAction {
+    /// Rename a tag on every task
+    Rename {
+        /// Current tag
+        old: String,
+
+        /// New tag
+        new: String,
+    },
+
+    /// Merge one tag into another
+    Merge {
+        /// Tag to fold away
+        from: String,
+
+        /// Tag to keep
+        into: String,
+    },
 }
 
 impl Cli {
@@ -143,6 +446,215 @@ impl Cli {
             None
         }
     }
+
+    /// Row layout given with `--columns` or `--template`, if any.
+    pub fn get_layout(&self) -> Result<Option<Layout>, String> {
+        if let Some(ref template) = self.template {
+            Layout::parse_template(template).map(Some)
+        } else if !self.columns.is_empty() {
+            Ok(Some(Layout::Columns(self.columns.clone())))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn get_output_format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else if self.jsonl {
+            OutputFormat::Jsonl
+        } else {
+            OutputFormat::Text
+        }
+    }
+}
+
+/// Parse an ID list such as `3`, `1-5` or `1-5,9`.
+pub fn parse_id_list(spec: &str) -> Result<Vec<u32>, String> {
+    const MAX_RANGE: u32 = 10_000;
+
+    let mut ids = Vec::new();
+
+    for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        if let Some((start, end)) = part.split_once('-') {
+            let invalid = || format!("Invalid ID range \"{}\"", part);
+            let start: u32 = start.trim().parse().map_err(|_| invalid())?;
+            let end: u32 = end.trim().parse().map_err(|_| invalid())?;
+            if start > end || end - start > MAX_RANGE {
+                return Err(invalid());
+            }
+            ids.extend(start..=end);
+        } else {
+            let id = part.parse().map_err(|_| format!("Invalid task ID \"{}\"", part))?;
+            ids.push(id);
+        }
+    }
+
+    Ok(ids)
+}
+
+/// Parse task references: numeric handles and ranges (`1-5,9`), full UUIDs
+/// and UUID prefixes (at least 4 characters), separated by commas. Digits
+/// alone are a handle unless they start with a zero; `uuid:1234` forces a
+/// UUID prefix.
+pub fn parse_task_refs(spec: &str) -> Result<Vec<TaskRef>, String> {
+    let mut refs = Vec::new();
+
+    for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        if let Some(prefix) = part.strip_prefix("uuid:") {
+            if prefix.len() < 4 || !prefix.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+                return Err(format!("Invalid task ID \"{}\"", part));
+            }
+            refs.push(TaskRef::Uuid(prefix.to_lowercase()));
+            continue;
+        }
+
+        let is_uuid = part.len() >= 4 && part.chars().all(|c| c.is_ascii_hexdigit() || c == '-');
+        let digits = part.chars().all(|c| c.is_ascii_digit());
+        let zero_prefix = is_uuid && digits && part.starts_with('0');
+        let is_handle = (digits && !zero_prefix) || is_range(part);
+
+        if is_handle {
+            refs.extend(parse_id_list(part)?.into_iter().map(TaskRef::Handle));
+        } else if is_uuid {
+            refs.push(TaskRef::Uuid(part.to_lowercase()));
+        } else {
+            return Err(format!("Invalid task ID \"{}\"", part));
+        }
+    }
+
+    Ok(refs)
+}
+
+/// `N-M` with short numbers. A UUID's first dash comes after 8 characters,
+/// so `12345678-1234` is a UUID prefix rather than a range.
+fn is_range(part: &str) -> bool {
+    part.split_once('-').is_some_and(|(start, end)| {
+        [start, end]
+            .iter()
+            .all(|n| !n.is_empty() && n.len() < 8 && n.chars().all(|c| c.is_ascii_digit()))
+    })
+}
+
+/// Words left after `parse_quick_add` pulls out inline priority and deadline.
+pub struct QuickAdd {
+    pub words: Vec<String>,
+    pub priority: Option<u8>,
+    pub time: Option<String>,
+}
+
+/// Longest run of words after an explicit deadline token that still parses.
+const MAX_DEADLINE_WORDS: usize = 4;
+
+/// Extract inline quick-add tokens from task words:
+///
+/// - `!1`..`!3`, or `!!!`/`!!`/`!` for high/medium/low priority
+/// - `due:friday` or `^tomorrow 3pm` for a deadline
+/// - a trailing `by friday 5pm` or `due friday` phrase, if it parses and
+///   `natural` is set
+///
+/// Words starting with `\` are always kept as literal text.
+pub fn parse_quick_add(parts: &[String], natural: bool) -> QuickAdd {
+    let words: Vec<&str> = parts.iter().flat_map(|p| p.split_whitespace()).collect();
+    let mut kept = Vec::new();
+    let mut priority = None;
+    let mut time = None;
+
+    let mut i = 0;
+    while i < words.len() {
+        let word = words[i];
+        i += 1;
+
+        if let Some(p) = parse_priority_token(word) {
+            priority = Some(p);
+            continue;
+        }
+
+        let deadline = word
+            .strip_prefix("due:")
+            .or_else(|| word.strip_prefix("^"))
+            .filter(|d| !d.is_empty());
+        if let Some(first) = deadline {
+            // Take as many following words as still form a valid time, so
+            // `^tomorrow 3pm` works but `^friday call bob` stops early
+            let following: Vec<&str> = words[i..]
+                .iter()
+                .take(MAX_DEADLINE_WORDS)
+                .take_while(|w| !is_marker(w))
+                .copied()
+                .collect();
+            let extra = (1..=following.len())
+                .rev()
+                .find(|&n| parse_deadline(&format!("{} {}", first, following[..n].join(" "))).is_some())
+                .unwrap_or(0);
+
+            let mut value = vec![first];
+            value.extend(&following[..extra]);
+            time = Some(value.join(" "));
+            i += extra;
+            continue;
+        }
+
+        kept.push(word.to_string());
+    }
+
+    if time.is_none() && natural {
+        if let Some((phrase, value)) = trailing_deadline(&kept) {
+            time = Some(value);
+            kept = kept
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| !phrase.contains(i))
+                .map(|(_, w)| w)
+                .collect();
+        }
+    }
+
+    QuickAdd {
+        words: kept,
+        priority,
+        time,
+    }
+}
+
+fn parse_priority_token(word: &str) -> Option<u8> {
+    match word {
+        "!1" | "!!!" => Some(1),
+        "!2" | "!!" => Some(2),
+        "!3" | "!" => Some(3),
+        _ => None,
+    }
+}
+
+/// Tags and projects, which may follow a trailing deadline phrase.
+fn is_marker(word: &str) -> bool {
+    (word.starts_with('+') || word.starts_with('@')) && word.len() > 1
+}
+
+/// Find a trailing `by <time>` or `due <time>` phrase, ignoring tags and
+/// projects after it. Returns the phrase's word indices and the time.
+fn trailing_deadline(words: &[String]) -> Option<(Vec<usize>, String)> {
+    let plain: Vec<usize> = (0..words.len()).filter(|&i| !is_marker(&words[i])).collect();
+
+    // Scan back from the end; the keyword can't be the first word
+    let start = plain.len().saturating_sub(MAX_DEADLINE_WORDS + 1).max(1);
+    for k in (start..plain.len()).rev() {
+        let keyword = words[plain[k]].to_lowercase();
+        if keyword != "by" && keyword != "due" {
+            continue;
+        }
+
+        let phrase = &plain[k + 1..];
+        if phrase.is_empty() {
+            continue;
+        }
+        let value = phrase.iter().map(|&i| words[i].as_str()).collect::<Vec<_>>().join(" ");
+        if parse_deadline(&value).is_some() {
+            return Some((plain[k..].to_vec(), value));
+        }
+    }
+
+    None
 }
 
 pub fn parse_tags_from_text(parts: &[String]) -> (String, Vec<String>) {
@@ -159,7 +671,8 @@ pub fn parse_tags_from_text(parts: &[String]) -> (String, Vec<String>) {
             // Skip project markers - handled separately
             continue;
         } else {
-            text_parts.push(part.clone());
+            // A leading backslash escapes quick-add syntax: \!1, \+1, \by
+            text_parts.push(part.strip_prefix('\\').unwrap_or(part).to_string());
         }
     }
 
@@ -193,3 +706,115 @@ pub fn parse_tag_modifications(parts: &[String]) -> (Vec<String>, Vec<String>) {
 
     (add_tags, remove_tags)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handles(ids: &[u32]) -> Vec<TaskRef> {
+        ids.iter().copied().map(TaskRef::Handle).collect()
+    }
+
+    fn uuid(prefix: &str) -> Vec<TaskRef> {
+        vec![TaskRef::Uuid(prefix.to_string())]
+    }
+
+    #[test]
+    fn task_refs() {
+        assert_eq!(parse_task_refs("3"), Ok(handles(&[3])));
+        assert_eq!(parse_task_refs("1-3,9"), Ok(handles(&[1, 2, 3, 9])));
+        assert_eq!(parse_task_refs("0-2"), Ok(handles(&[0, 1, 2])));
+        assert_eq!(parse_task_refs("1234"), Ok(handles(&[1234])));
+        assert_eq!(parse_task_refs("3F2A"), Ok(uuid("3f2a")));
+        assert!(parse_task_refs("1-20000").is_err());
+        assert!(parse_task_refs("3-1").is_err());
+        assert!(parse_task_refs("xyz").is_err());
+    }
+
+    #[test]
+    fn digit_only_uuid_prefixes() {
+        assert_eq!(parse_task_refs("0123"), Ok(uuid("0123")));
+        assert_eq!(parse_task_refs("uuid:1234"), Ok(uuid("1234")));
+        assert_eq!(parse_task_refs("12345678-1234"), Ok(uuid("12345678-1234")));
+        assert_eq!(
+            parse_task_refs("12345678-1234-1234-1234-123456789012"),
+            Ok(uuid("12345678-1234-1234-1234-123456789012"))
+        );
+        assert!(parse_task_refs("uuid:12").is_err());
+    }
+
+    fn words(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_string).collect()
+    }
+
+    fn quick_add(s: &str) -> QuickAdd {
+        parse_quick_add(&words(s), true)
+    }
+
+    #[test]
+    fn priority_tokens() {
+        let add = quick_add("ship it !1");
+        assert_eq!(add.priority, Some(1));
+        assert_eq!(add.words, words("ship it"));
+
+        assert_eq!(quick_add("!!! ship it").priority, Some(1));
+        assert_eq!(quick_add("ship !! it").priority, Some(2));
+        assert_eq!(quick_add("ship it !").priority, Some(3));
+        assert_eq!(quick_add("ship it").priority, None);
+    }
+
+    #[test]
+    fn explicit_deadlines() {
+        let add = quick_add("report due:tomorrow 3pm +work");
+        assert_eq!(add.time.as_deref(), Some("tomorrow 3pm"));
+        assert_eq!(add.words, words("report +work"));
+
+        // Stops at the first word that no longer parses
+        let add = quick_add("^friday call bob");
+        assert_eq!(add.time.as_deref(), Some("friday"));
+        assert_eq!(add.words, words("call bob"));
+
+        let add = quick_add("^next monday 9am standup");
+        assert_eq!(add.time.as_deref(), Some("next monday 9am"));
+        assert_eq!(add.words, words("standup"));
+    }
+
+    #[test]
+    fn trailing_phrases() {
+        let add = quick_add("call bob by friday 5pm +work");
+        assert_eq!(add.time.as_deref(), Some("friday 5pm"));
+        assert_eq!(add.words, words("call bob +work"));
+
+        let add = quick_add("pay rent due tomorrow");
+        assert_eq!(add.time.as_deref(), Some("tomorrow"));
+        assert_eq!(add.words, words("pay rent"));
+
+        let add = quick_add("walk by the river");
+        assert_eq!(add.time, None);
+        assert_eq!(add.words, words("walk by the river"));
+
+        // Only with natural parsing on
+        assert_eq!(parse_quick_add(&words("call bob by friday"), false).time, None);
+        assert_eq!(
+            trailing_deadline(&words("call bob by friday +work")),
+            Some((vec![2, 3], "friday".to_string()))
+        );
+        // The keyword can't be the first word
+        assert_eq!(trailing_deadline(&words("by friday")), None);
+    }
+
+    #[test]
+    fn escaped_words_stay_literal() {
+        let add = quick_add("\\!1 is literal");
+        assert_eq!(add.priority, None);
+        assert_eq!(add.words, words("\\!1 is literal"));
+
+        let add = quick_add("stand \\by friday");
+        assert_eq!(add.time, None);
+        assert_eq!(add.words, words("stand \\by friday"));
+
+        let add = quick_add("read \\due:friday");
+        assert_eq!(add.time, None);
+        assert_eq!(add.words, words("read \\due:friday"));
+    }
+}
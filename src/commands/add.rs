@@ -1,41 +1,66 @@
-use crate::cli::{parse_project_from_text, parse_tags_from_text};
-use crate::display::{print_error, print_todo_added, DisplayConfig};
+use crate::activity::{self, Action};
+use crate::cli::{parse_project_from_text, parse_quick_add, parse_tags_from_text};
+use crate::display::{print_error, print_mutation, print_todo_added, warn_if_past, DisplayConfig};
+use crate::reminder::Reminder;
+use crate::rules::TagRules;
 use crate::store::Store;
-use crate::time::parse_time;
+use crate::time::{parse_deadline, parse_duration};
 use crate::todo::Todo;
 
-pub fn run(
-    text: Vec<String>,
-    priority: Option<u8>,
-    time: Option<String>,
-    store: &mut Store,
-    config: &DisplayConfig,
-) {
+pub struct AddOptions {
+    pub text: Vec<String>,
+    pub priority: Option<u8>,
+    pub time: Option<String>,
+    pub estimate: Option<String>,
+    pub remind: Vec<String>,
+}
+
+pub fn run(opts: AddOptions, store: &mut Store, config: &DisplayConfig, rules: &TagRules) {
+    let AddOptions {
+        text,
+        priority,
+        time,
+        estimate,
+        remind,
+    } = opts;
+
     if text.is_empty() {
         print_error("Task text is required");
         return;
     }
 
+    // Inline `!1`, `due:friday`, `by friday 5pm`; flags take precedence
+    let quick = parse_quick_add(&text, time.is_none());
+    let text = quick.words;
+    let priority = priority.or(quick.priority);
+    let time = time.or(quick.time);
+
     // Validate priority
     if let Some(p) = priority {
-        if p < 1 || p > 3 {
+        if !(1..=3).contains(&p) {
             print_error("Priority must be 1, 2, or 3");
             return;
         }
     }
 
     // Parse text and extract tags and project
-    let (task_text, tags) = parse_tags_from_text(&text);
-    let project = parse_project_from_text(&text);
+    let (task_text, mut tags) = parse_tags_from_text(&text);
+    let mut project = parse_project_from_text(&text);
 
     if task_text.is_empty() {
         print_error("Task text is required");
         return;
     }
 
+    // Apply configured tagging rules; an explicit @project wins
+    let (rule_tags, rule_project) = rules.match_text(&task_text);
+    tags.extend(rule_tags);
+    let tags = rules.expand(&tags);
+    project = project.or(rule_project);
+
     // Parse deadline
     let deadline = if let Some(t) = time {
-        match parse_time(&t) {
+        match parse_deadline(&t) {
             Some(dt) => Some(dt),
             None => {
                 print_error(&format!("Could not parse time \"{}\"", t));
@@ -46,16 +71,63 @@ pub fn run(
         None
     };
 
+    let estimate = if let Some(e) = estimate {
+        match parse_duration(&e) {
+            Some(d) => match u32::try_from(d.num_minutes()) {
+                Ok(minutes) => Some(minutes),
+                Err(_) => {
+                    print_error(&format!("Estimate \"{}\" is too long", e));
+                    return;
+                }
+            },
+            None => {
+                print_error(&format!("Could not parse estimate \"{}\"", e));
+                return;
+            }
+        }
+    } else {
+        None
+    };
+
+    let reminders = match parse_reminders(&remind) {
+        Ok(reminders) => reminders,
+        Err(e) => {
+            print_error(&e);
+            return;
+        }
+    };
+    if deadline.is_none() {
+        if let Some(spec) = remind.iter().zip(&reminders).find(|(_, r)| matches!(r, Reminder::Before(_))) {
+            print_error(&format!("Reminder \"{}\" needs a deadline", spec.0));
+            return;
+        }
+    }
+
     let todo = Todo::new(0, task_text)
         .with_priority(priority)
         .with_deadline(deadline)
         .with_tags(tags)
-        .with_project(project);
+        .with_project(project)
+        .with_estimate(estimate)
+        .with_reminders(reminders);
 
-    let added = store.add(todo);
-    print_todo_added(added, config);
+    let added = store.add(todo).clone();
+    print_todo_added(&added, config);
+    warn_if_past(added.deadline);
 
     if let Err(e) = store.save_with_undo() {
         print_error(&format!("Could not save: {}", e));
+        return;
     }
+
+    activity::record(Action::Added, std::slice::from_ref(&added));
+    print_mutation("added", &[added], config);
+}
+
+/// Parse `--remind` values.
+pub fn parse_reminders(specs: &[String]) -> Result<Vec<Reminder>, String> {
+    specs
+        .iter()
+        .map(|spec| Reminder::parse(spec).ok_or_else(|| format!("Could not parse reminder \"{}\"", spec)))
+        .collect()
 }
@@ -1,9 +1,24 @@
-use crate::display::{print_error, DisplayConfig};
+use crate::activity::{self, Action};
+use crate::display::{print_error, print_mutation, DisplayConfig};
 use crate::store::Store;
 use colored::Colorize;
 
 pub fn run(store: &mut Store, config: &DisplayConfig) {
-    let count = store.clear_completed();
+    let cleared = store.clear_completed();
+    let count = cleared.len();
+
+    if count > 0 {
+        if let Err(e) = store.save_with_undo() {
+            print_error(&format!("Could not save: {}", e));
+            return;
+        }
+        activity::record(Action::Cleared, &cleared);
+    }
+
+    if config.is_machine() {
+        print_mutation("cleared", &cleared, config);
+        return;
+    }
 
     if count == 0 {
         println!("No completed tasks to clear.");
@@ -17,8 +32,4 @@ pub fn run(store: &mut Store, config: &DisplayConfig) {
     } else {
         println!("{}", msg);
     }
-
-    if let Err(e) = store.save_with_undo() {
-        print_error(&format!("Could not save: {}", e));
-    }
 }
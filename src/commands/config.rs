@@ -0,0 +1,168 @@
+use crate::config::{self, Config, Layer, SETTINGS};
+use crate::display::{print_error, print_json, print_json_list, DisplayConfig};
+use colored::Colorize;
+use serde_json::json;
+
+/// A setting's effective value and where it comes from.
+struct Entry {
+    key: String,
+    /// None if the setting is unset
+    value: Option<toml::Value>,
+    /// The file that sets it, or None for the default
+    source: Option<String>,
+}
+
+impl Entry {
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "key": self.key,
+            "value": self.value,
+            "source": self.source.as_deref().unwrap_or("default"),
+        })
+    }
+}
+
+/// `tsk config list`: known settings first, then anything else the files set.
+pub fn list(config: &DisplayConfig) {
+    let Some(layers) = load_layers() else {
+        return;
+    };
+
+    let mut entries: Vec<Entry> = SETTINGS.iter().map(|(key, _)| lookup(&layers, key)).collect();
+    for layer in &layers {
+        let mut leaves = Vec::new();
+        flatten("", &layer.table, &mut leaves);
+        for key in leaves {
+            if !entries.iter().any(|e| e.key == key) {
+                entries.push(lookup(&layers, &key));
+            }
+        }
+    }
+
+    if config.is_machine() {
+        let entries: Vec<_> = entries.iter().map(Entry::to_json).collect();
+        print_json_list(&entries, config);
+        return;
+    }
+
+    let lines: Vec<(String, String)> = entries
+        .iter()
+        .map(|e| match (&e.value, &e.source) {
+            (Some(value), Some(source)) => (format!("{} = {}", e.key, value), source.clone()),
+            (Some(value), None) => (format!("{} = {}", e.key, value), "default".to_string()),
+            (None, _) => (e.key.clone(), "not set".to_string()),
+        })
+        .collect();
+    let width = lines.iter().map(|(line, _)| line.len()).max().unwrap_or(0);
+
+    for (line, note) in lines {
+        let note = format!("# {}", note);
+        if config.use_color {
+            println!("{:<width$}  {}", line, note.dimmed(), width = width);
+        } else {
+            println!("{:<width$}  {}", line, note, width = width);
+        }
+    }
+}
+
+/// `tsk config get <key>`: a single value, or a whole section as TOML.
+pub fn get(key: &str, config: &DisplayConfig) {
+    let Some(layers) = load_layers() else {
+        return;
+    };
+
+    let entry = lookup(&layers, key);
+    let Some(value) = &entry.value else {
+        if SETTINGS.iter().any(|(k, _)| *k == key) || ["views.", "tags."].iter().any(|p| key.starts_with(p)) {
+            print_error(&format!("{} is not set", key));
+        } else {
+            print_error(&format!("Unknown setting \"{}\"", key));
+        }
+        return;
+    };
+
+    if config.is_machine() {
+        print_json(&entry.to_json(), config);
+        return;
+    }
+
+    match value {
+        toml::Value::String(s) => println!("{}", s),
+        toml::Value::Table(table) => print!("{}", toml::to_string(table).unwrap_or_default()),
+        value => println!("{}", value),
+    }
+}
+
+/// `tsk config set <key> <value> [--local]`.
+pub fn set(key: &str, value: &str, local: bool, config: &DisplayConfig) {
+    let path = match config::set_value(key, value, local) {
+        Ok(path) => path,
+        Err(e) => {
+            print_error(&format!("Invalid config: {}", e));
+            return;
+        }
+    };
+
+    if config.is_machine() {
+        print_json(&json!({ "status": "ok", "key": key, "file": path }), config);
+        return;
+    }
+
+    let msg = format!("Set {} in {}", key, path.display());
+    if config.use_color {
+        println!("{}", msg.green());
+    } else {
+        println!("{}", msg);
+    }
+}
+
+fn load_layers() -> Option<Vec<Layer>> {
+    match Config::layers() {
+        Ok(layers) => Some(layers),
+        Err(e) => {
+            print_error(&format!("Invalid config: {}", e));
+            None
+        }
+    }
+}
+
+/// The value of `key` from the last file that sets it, else its default.
+fn lookup(layers: &[Layer], key: &str) -> Entry {
+    for layer in layers.iter().rev() {
+        let mut value = Some(&layer.table);
+        let mut found = None;
+        for part in key.split('.') {
+            found = value.and_then(|table| table.get(part));
+            value = found.and_then(|v| v.as_table());
+        }
+        if let Some(found) = found {
+            return Entry {
+                key: key.to_string(),
+                value: Some(found.clone()),
+                source: Some(layer.path.display().to_string()),
+            };
+        }
+    }
+
+    let default = SETTINGS
+        .iter()
+        .find(|(k, _)| *k == key)
+        .and_then(|(_, default)| toml::from_str::<toml::Table>(&format!("value = {}", default)).ok())
+        .and_then(|mut table| table.remove("value"));
+    Entry {
+        key: key.to_string(),
+        value: default,
+        source: None,
+    }
+}
+
+/// Dotted keys of every non-table value in `table`.
+fn flatten(prefix: &str, table: &toml::Table, out: &mut Vec<String>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match value {
+            toml::Value::Table(table) => flatten(&key, table, out),
+            _ => out.push(key),
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use crate::display::{print_error, print_json, DisplayConfig};
+use crate::store::Store;
+use crate::time::format_deadline;
+use crate::todo::Todo;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+
+/// Reminders missed by more than this while the daemon wasn't running are
+/// dropped rather than sent late.
+const MISSED_LIMIT_HOURS: i64 = 24;
+
+/// Fire times by task UUID.
+type Fired = BTreeMap<String, BTreeSet<DateTime<Utc>>>;
+
+/// Reminders already sent, kept in `~/.tsk/reminders.json` so a restart
+/// doesn't repeat them.
+#[derive(Default, Serialize, Deserialize)]
+struct FiredState {
+    /// Fire times already handled
+    fired: Fired,
+}
+
+impl FiredState {
+    fn path() -> io::Result<PathBuf> {
+        Ok(Store::data_dir()?.join("reminders.json"))
+    }
+
+    fn load() -> io::Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content =
+            serde_json::to_string_pretty(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(&path, content)
+    }
+}
+
+pub fn run(interval: u64, once: bool, command: Option<&[String]>, config: &DisplayConfig) {
+    let mut state = match FiredState::load() {
+        Ok(state) => state,
+        Err(e) => {
+            print_error(&format!("Could not load reminder state: {}", e));
+            return;
+        }
+    };
+
+    loop {
+        // Re-read the store each round to pick up changes from other commands
+        match Store::load() {
+            Ok(store) => check(&store, &mut state, command, config),
+            Err(e) => print_error(&format!("Could not load tasks: {}", e)),
+        }
+
+        if once {
+            return;
+        }
+        thread::sleep(std::time::Duration::from_secs(interval.max(1)));
+    }
+}
+
+/// Send every reminder that has come due and not been sent yet.
+fn check(store: &Store, state: &mut FiredState, command: Option<&[String]>, config: &DisplayConfig) {
+    let (due, to_send) = due_reminders(store, &state.fired, Utc::now());
+    for todo in to_send {
+        notify(todo, command, config);
+    }
+
+    // Only reminders still on open tasks need remembering
+    let changed = due != state.fired;
+    state.fired = due;
+
+    if changed {
+        if let Err(e) = state.save() {
+            print_error(&format!("Could not save reminder state: {}", e));
+        }
+    }
+}
+
+/// Fire times of reminders on open tasks that have come due by `now`, by
+/// task UUID, and the tasks to notify: one per reminder not yet in `fired`
+/// and missed by less than `MISSED_LIMIT_HOURS`.
+fn due_reminders<'a>(store: &'a Store, fired: &Fired, now: DateTime<Utc>) -> (Fired, Vec<&'a Todo>) {
+    let mut due = Fired::new();
+    let mut to_send = Vec::new();
+
+    for todo in store.todos.iter().filter(|t| !t.done) {
+        for reminder in &todo.reminders {
+            let Some(at) = reminder.fire_time(todo.deadline) else {
+                continue;
+            };
+            if at > now {
+                continue;
+            }
+            due.entry(todo.uuid.clone()).or_default().insert(at);
+
+            let sent = fired.get(&todo.uuid).is_some_and(|fired| fired.contains(&at));
+            if !sent && now - at < Duration::hours(MISSED_LIMIT_HOURS) {
+                to_send.push(todo);
+            }
+        }
+    }
+
+    (due, to_send)
+}
+
+/// Run the configured command, or print the reminder if there is none.
+///
+/// `{id}`, `{uuid}`, `{text}` and `{due}` in the command's arguments are
+/// replaced, and the same values are set as `TSK_ID`, `TSK_UUID`,
+/// `TSK_TEXT` and `TSK_DUE`.
+fn notify(todo: &Todo, command: Option<&[String]>, config: &DisplayConfig) {
+    let due = todo.deadline.map(|d| format_deadline(d, false)).unwrap_or_default();
+    let fields = [
+        ("id", todo.id.to_string()),
+        ("uuid", todo.uuid.clone()),
+        ("text", todo.text.clone()),
+        ("due", due.clone()),
+    ];
+
+    let Some(command) = command else {
+        if config.is_machine() {
+            print_json(
+                &json!({
+                    "status": "reminder",
+                    "id": todo.id,
+                    "uuid": todo.uuid,
+                    "text": todo.text,
+                    "deadline": todo.deadline,
+                }),
+                config,
+            );
+        } else if due.is_empty() {
+            println!("Reminder: #{} {}", todo.id, todo.text);
+        } else {
+            println!("Reminder: #{} {} (due {})", todo.id, todo.text, due);
+        }
+        return;
+    };
+
+    let args: Vec<String> = command
+        .iter()
+        .map(|word| {
+            fields
+                .iter()
+                .fold(word.clone(), |word, (key, value)| word.replace(&format!("{{{}}}", key), value))
+        })
+        .collect();
+
+    let mut child = process::Command::new(&args[0]);
+    child.args(&args[1..]);
+    for (key, value) in &fields {
+        child.env(format!("TSK_{}", key.to_uppercase()), value);
+    }
+
+    match child.status() {
+        Ok(status) if !status.success() => {
+            print_error(&format!("Reminder command for #{} exited with {}", todo.id, status))
+        }
+        Ok(_) => {}
+        Err(e) => print_error(&format!("Could not run reminder command: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deadline::Deadline;
+    use crate::reminder::Reminder;
+
+    fn task(id: u32, reminders: Vec<Reminder>) -> Todo {
+        let mut todo = Todo::new(id, format!("task {}", id));
+        todo.reminders = reminders;
+        todo
+    }
+
+    fn store(todos: Vec<Todo>) -> Store {
+        Store {
+            todos,
+            ..Store::default()
+        }
+    }
+
+    fn ids(todos: &[&Todo]) -> Vec<u32> {
+        todos.iter().map(|t| t.id).collect()
+    }
+
+    #[test]
+    fn sends_due_reminders_once() {
+        let now = Utc::now();
+        let at = now - Duration::minutes(5);
+        let store = store(vec![
+            task(1, vec![Reminder::At(Deadline::at(at))]),
+            task(2, vec![Reminder::At(Deadline::at(now + Duration::hours(1)))]),
+        ]);
+
+        let (due, to_send) = due_reminders(&store, &Fired::new(), now);
+        assert_eq!(ids(&to_send), vec![1]);
+        assert_eq!(due.len(), 1);
+
+        // Already recorded as fired: remembered, but not sent again
+        let (again, to_send) = due_reminders(&store, &due, now);
+        assert!(to_send.is_empty());
+        assert_eq!(again, due);
+    }
+
+    #[test]
+    fn skips_long_missed_reminders() {
+        let now = Utc::now();
+        let at = now - Duration::hours(MISSED_LIMIT_HOURS + 1);
+        let store = store(vec![task(1, vec![Reminder::At(Deadline::at(at))])]);
+
+        let (due, to_send) = due_reminders(&store, &Fired::new(), now);
+        assert!(to_send.is_empty());
+        // Recorded so it isn't reconsidered
+        assert!(due[&store.todos[0].uuid].contains(&at));
+    }
+
+    #[test]
+    fn ignores_done_tasks_and_offsets_without_deadline() {
+        let now = Utc::now();
+        let mut done = task(1, vec![Reminder::At(Deadline::at(now - Duration::minutes(1)))]);
+        done.done = true;
+        let store = store(vec![done, task(2, vec![Reminder::Before(15)])]);
+
+        let (due, to_send) = due_reminders(&store, &Fired::new(), now);
+        assert!(to_send.is_empty());
+        assert!(due.is_empty());
+    }
+}
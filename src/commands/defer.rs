@@ -0,0 +1,144 @@
+use crate::activity::{self, Action};
+use crate::deadline::Deadline;
+use crate::display::{print_error, print_mutation, print_todo_rescheduled, DisplayConfig};
+use crate::select::{confirm_bulk, Selector};
+use crate::store::Store;
+use crate::time::{parse_deadline, shift_deadline};
+use chrono::Local;
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Snooze,
+    Defer,
+}
+
+impl Kind {
+    fn verb(self) -> &'static str {
+        match self {
+            Kind::Snooze => "snooze",
+            Kind::Defer => "defer",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Kind::Snooze => "Snoozed",
+            Kind::Defer => "Deferred",
+        }
+    }
+
+    fn action(self) -> Action {
+        match self {
+            Kind::Snooze => Action::Snoozed,
+            Kind::Defer => Action::Deferred,
+        }
+    }
+
+    fn past(self) -> &'static str {
+        match self {
+            Kind::Snooze => "snoozed",
+            Kind::Defer => "deferred",
+        }
+    }
+}
+
+/// Set deadlines to a duration from now: `tsk snooze 3 2h`.
+pub fn snooze(selector: Selector, duration: &str, yes: bool, store: &mut Store, config: &DisplayConfig) {
+    if selector.is_empty() {
+        print_error("At least one task ID or filter is required");
+        return;
+    }
+
+    // Same grammar as "in 2 hours", so "2h", "90 min" and "1 day" all work
+    let Some(deadline) = parse_deadline(&format!("in {}", duration)) else {
+        print_error(&format!("Could not parse duration \"{}\"", duration));
+        return;
+    };
+
+    reschedule(selector, Kind::Snooze, yes, store, config, |_| Some(deadline));
+}
+
+/// Set deadlines to a new time (`monday`), or shift the current ones by an
+/// offset (`+2d`, `-1w`). With `keep_time`, a date keeps each task's time of
+/// day.
+pub fn defer(selector: Selector, when: &str, keep_time: bool, yes: bool, store: &mut Store, config: &DisplayConfig) {
+    if selector.is_empty() {
+        print_error("At least one task ID or filter is required");
+        return;
+    }
+
+    if when.starts_with('+') || when.starts_with('-') {
+        // Tasks without a deadline are shifted from today
+        let today = Deadline::Day(Local::now().date_naive());
+        if shift_deadline(today, when).is_none() {
+            print_error(&format!("Could not parse offset \"{}\"", when));
+            return;
+        }
+
+        reschedule(selector, Kind::Defer, yes, store, config, |current| {
+            shift_deadline(current.unwrap_or(today), when)
+        });
+        return;
+    }
+
+    let Some(deadline) = parse_deadline(when) else {
+        print_error(&format!("Could not parse time \"{}\"", when));
+        return;
+    };
+
+    reschedule(selector, Kind::Defer, yes, store, config, |current| match (deadline, current) {
+        (Deadline::Day(date), Some(current)) if keep_time => current.on_date(date),
+        _ => Some(deadline),
+    });
+}
+
+fn reschedule(
+    selector: Selector,
+    kind: Kind,
+    yes: bool,
+    store: &mut Store,
+    config: &DisplayConfig,
+    new_deadline: impl Fn(Option<Deadline>) -> Option<Deadline>,
+) {
+    let selection = selector.resolve(store);
+    if !selection.report() {
+        return;
+    }
+
+    match confirm_bulk(kind.verb(), &selection.ids, yes, store, config) {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(e) => {
+            print_error(&e);
+            return;
+        }
+    }
+
+    let mut updated = Vec::new();
+
+    for id in selection.ids {
+        let Some(todo) = store.get_mut(id) else {
+            continue;
+        };
+        match new_deadline(todo.deadline) {
+            Some(deadline) => {
+                let before = todo.clone();
+                todo.deadline = Some(deadline);
+                todo.record_changes(&before);
+                print_todo_rescheduled(kind.label(), todo, config);
+                updated.push(todo.clone());
+            }
+            None => print_error(&format!("Could not move the deadline of #{}", id)),
+        }
+    }
+
+    if !updated.is_empty() {
+        if let Err(e) = store.save_with_undo() {
+            print_error(&format!("Could not save: {}", e));
+            return;
+        }
+        activity::record(kind.action(), &updated);
+    }
+
+    print_mutation(kind.past(), &updated, config);
+}
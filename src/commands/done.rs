@@ -1,31 +1,51 @@
-use crate::display::{print_error, print_todo_completed, DisplayConfig};
+use crate::activity::{self, Action};
+use crate::display::{print_error, print_mutation, print_todo_completed, DisplayConfig};
+use crate::select::{confirm_bulk, Selector};
 use crate::store::Store;
 
-pub fn run(ids: Vec<u32>, store: &mut Store, config: &DisplayConfig) {
-    if ids.is_empty() {
-        print_error("At least one task ID is required");
+pub fn run(selector: Selector, yes: bool, store: &mut Store, config: &DisplayConfig) {
+    if selector.is_empty() {
+        print_error("At least one task ID or filter is required");
         return;
     }
 
-    let mut success = false;
+    let selection = selector.resolve(store);
+    if !selection.report() {
+        return;
+    }
 
-    for id in ids {
+    match confirm_bulk("complete", &selection.ids, yes, store, config) {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(e) => {
+            print_error(&e);
+            return;
+        }
+    }
+
+    let mut completed = Vec::new();
+
+    for id in selection.ids {
         if let Some(todo) = store.get_mut(id) {
             if todo.done {
-                println!("Task #{} is already completed", id);
+                if !config.is_machine() {
+                    println!("Task #{} is already completed", id);
+                }
             } else {
                 todo.mark_done();
                 print_todo_completed(todo, config);
-                success = true;
+                completed.push(todo.clone());
             }
-        } else {
-            print_error(&format!("Task #{} not found", id));
         }
     }
 
-    if success {
+    if !completed.is_empty() {
         if let Err(e) = store.save_with_undo() {
             print_error(&format!("Could not save: {}", e));
+            return;
         }
+        activity::record(Action::Completed, &completed);
     }
+
+    print_mutation("completed", &completed, config);
 }
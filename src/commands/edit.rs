@@ -1,37 +1,90 @@
+use crate::activity::{self, Action};
 use crate::cli::{parse_project_from_text, parse_tag_modifications};
-use crate::display::{print_error, print_todo_updated, DisplayConfig};
+use crate::commands::add::parse_reminders;
+use crate::display::{print_error, print_mutation, print_todo_updated, print_warning, warn_if_past, DisplayConfig};
+use crate::reminder::Reminder;
+use crate::rules::TagRules;
+use crate::select::{confirm_bulk, Selector};
 use crate::store::Store;
-use crate::time::parse_time;
-
-pub fn run(
-    id: u32,
-    text: Vec<String>,
-    priority: Option<u8>,
-    time: Option<String>,
-    clear_time: bool,
-    clear_priority: bool,
-    clear_project: bool,
-    store: &mut Store,
-    config: &DisplayConfig,
-) {
+use crate::time::{parse_deadline, parse_duration};
+
+/// Changes requested by `tsk edit`.
+pub struct EditOptions {
+    pub text: Vec<String>,
+    pub priority: Option<u8>,
+    pub time: Option<String>,
+    pub clear_time: bool,
+    pub clear_priority: bool,
+    pub clear_project: bool,
+    pub estimate: Option<String>,
+    pub clear_estimate: bool,
+    pub remind: Vec<String>,
+    pub clear_reminders: bool,
+}
+
+pub fn run(selector: Selector, opts: EditOptions, yes: bool, store: &mut Store, config: &DisplayConfig, rules: &TagRules) {
+    let EditOptions {
+        text,
+        priority,
+        time,
+        clear_time,
+        clear_priority,
+        clear_project,
+        estimate,
+        clear_estimate,
+        remind,
+        clear_reminders,
+    } = opts;
+
     // Validate priority
     if let Some(p) = priority {
-        if p < 1 || p > 3 {
+        if !(1..=3).contains(&p) {
             print_error("Priority must be 1, 2, or 3");
             return;
         }
     }
 
-    let todo = match store.get_mut(id) {
-        Some(t) => t,
-        None => {
-            print_error(&format!("Task #{} not found", id));
+    // Parse deadline
+    let deadline = match time {
+        Some(t) if !clear_time => match parse_deadline(&t) {
+            Some(dt) => Some(dt),
+            None => {
+                print_error(&format!("Could not parse time \"{}\"", t));
+                return;
+            }
+        },
+        _ => None,
+    };
+
+    // Parse estimate
+    let estimate = match estimate {
+        Some(e) if !clear_estimate => match parse_duration(&e) {
+            Some(d) => match u32::try_from(d.num_minutes()) {
+                Ok(minutes) => Some(minutes),
+                Err(_) => {
+                    print_error(&format!("Estimate \"{}\" is too long", e));
+                    return;
+                }
+            },
+            None => {
+                print_error(&format!("Could not parse estimate \"{}\"", e));
+                return;
+            }
+        },
+        _ => None,
+    };
+
+    let reminders = match parse_reminders(&remind) {
+        Ok(reminders) => reminders,
+        Err(e) => {
+            print_error(&e);
             return;
         }
     };
 
     // Parse tag modifications from text
-    let (add_tags, remove_tags) = parse_tag_modifications(&text);
+    let (mut add_tags, remove_tags) = parse_tag_modifications(&text);
+    let remove_tags: Vec<String> = remove_tags.iter().map(|t| rules.resolve_alias(t)).collect();
 
     // Parse project from text
     let project = parse_project_from_text(&text);
@@ -44,49 +97,113 @@ pub fn run(
         .collect::<Vec<_>>()
         .join(" ");
 
-    // Update text if provided
+    // Pattern rules only look at new text, so removing a rule's tag sticks
+    let mut rule_project = None;
     if !new_text.is_empty() {
-        todo.text = new_text;
+        let (rule_tags, matched_project) = rules.match_text(&new_text);
+        add_tags.extend(rule_tags);
+        rule_project = matched_project;
     }
+    let add_tags = rules.expand(&add_tags);
 
-    // Update priority
-    if clear_priority {
-        todo.priority = None;
-    } else if let Some(p) = priority {
-        todo.priority = Some(p);
+    let selection = selector.resolve(store);
+    if !selection.report() {
+        return;
     }
 
-    // Update deadline
-    if clear_time {
-        todo.deadline = None;
-    } else if let Some(t) = time {
-        match parse_time(&t) {
-            Some(dt) => todo.deadline = Some(dt),
-            None => {
-                print_error(&format!("Could not parse time \"{}\"", t));
-                return;
-            }
-        }
+    if !new_text.is_empty() && selection.ids.len() > 1 {
+        print_error("New text can only be set on a single task");
+        return;
     }
 
-    // Update tags
-    for tag in add_tags {
-        todo.add_tag(tag);
-    }
-    for tag in remove_tags {
-        todo.remove_tag(&tag);
+    match confirm_bulk("edit", &selection.ids, yes, store, config) {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(e) => {
+            print_error(&e);
+            return;
+        }
     }
 
-    // Update project
-    if clear_project {
-        todo.project = None;
-    } else if project.is_some() {
-        todo.project = project;
-    }
+    let mut updated = Vec::new();
+
+    for id in selection.ids {
+        let Some(todo) = store.get_mut(id) else {
+            continue;
+        };
+        let before = todo.clone();
+
+        // Update text if provided
+        if !new_text.is_empty() {
+            todo.text = new_text.clone();
+        }
+
+        // Update priority
+        if clear_priority {
+            todo.priority = None;
+        } else if let Some(p) = priority {
+            todo.priority = Some(p);
+        }
 
-    print_todo_updated(todo, config);
+        // Update deadline
+        if clear_time {
+            todo.deadline = None;
+        } else if deadline.is_some() {
+            todo.deadline = deadline;
+        }
+
+        // Update estimate
+        if clear_estimate {
+            todo.estimate = None;
+        } else if estimate.is_some() {
+            todo.estimate = estimate;
+        }
 
+        // Update reminders
+        if clear_reminders {
+            todo.reminders.clear();
+        }
+        for reminder in &reminders {
+            if !todo.reminders.contains(reminder) {
+                todo.reminders.push(*reminder);
+            }
+        }
+
+        // Update tags
+        for tag in &add_tags {
+            todo.add_tag(tag.clone());
+        }
+        for tag in &remove_tags {
+            todo.remove_tag(tag);
+        }
+
+        // Update project
+        if clear_project {
+            todo.project = None;
+        } else if project.is_some() {
+            todo.project = project.clone();
+        } else if todo.project.is_none() {
+            todo.project = rule_project.clone();
+        }
+
+        todo.record_changes(&before);
+        print_todo_updated(todo, config);
+        updated.push(todo.clone());
+    }
+
+    // All changes land in one save, so a single undo reverts them together
     if let Err(e) = store.save_with_undo() {
         print_error(&format!("Could not save: {}", e));
+        return;
+    }
+    activity::record(Action::Edited, &updated);
+
+    warn_if_past(deadline);
+    let reminders_changed = !reminders.is_empty() || clear_time;
+    for todo in updated.iter().filter(|t| reminders_changed && t.deadline.is_none()) {
+        if todo.reminders.iter().any(|r| matches!(r, Reminder::Before(_))) {
+            print_warning(&format!("#{} has no deadline, so its reminders before the deadline won't fire", todo.id));
+        }
     }
+    print_mutation("updated", &updated, config);
 }
@@ -1,14 +1,28 @@
 use crate::cli::SortBy;
-use crate::display::{format_todo, print_empty_message, DisplayConfig};
+use crate::display::{print_empty_message, print_json_list, render_rows, DisplayConfig, View};
 use crate::store::Store;
-use crate::time::is_due_this_week;
+use crate::time::{is_due_this_week, is_due_today};
 use crate::todo::Todo;
 
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Filter {
     Open,
     All,
     Week,
     Overdue,
+    Today,
+}
+
+impl Filter {
+    fn view(self) -> View {
+        match self {
+            Filter::Open => View::List,
+            Filter::All => View::All,
+            Filter::Week => View::Week,
+            Filter::Overdue => View::Overdue,
+            Filter::Today => View::Today,
+        }
+    }
 }
 
 pub fn run(
@@ -19,6 +33,32 @@ pub fn run(
     tag_filters: &[String],
     project_filter: Option<&str>,
 ) {
+    let mut todos = select(store, filter, tag_filters, project_filter);
+
+    if todos.is_empty() && !config.is_machine() {
+        print_empty_message();
+        return;
+    }
+
+    sort(&mut todos, sort_by.unwrap_or(SortBy::Priority));
+
+    if config.is_machine() {
+        print_json_list(&todos, config);
+        return;
+    }
+
+    for row in render_rows(&todos, filter.view(), config) {
+        println!("{}", row);
+    }
+}
+
+/// Tasks matching a view filter plus optional tag and project filters.
+pub fn select<'a>(
+    store: &'a Store,
+    filter: Filter,
+    tag_filters: &[String],
+    project_filter: Option<&str>,
+) -> Vec<&'a Todo> {
     let mut todos: Vec<&Todo> = match filter {
         Filter::Open => store.open_todos().collect(),
         Filter::All => store.todos.iter().collect(),
@@ -27,6 +67,10 @@ pub fn run(
             .filter(|t| t.deadline.map(is_due_this_week).unwrap_or(false))
             .collect(),
         Filter::Overdue => store.open_todos().filter(|t| t.is_overdue()).collect(),
+        Filter::Today => store
+            .open_todos()
+            .filter(|t| t.is_overdue() || t.deadline.map(is_due_today).unwrap_or(false))
+            .collect(),
     };
 
     // Apply tag filters
@@ -39,13 +83,10 @@ pub fn run(
         todos.retain(|t| t.in_project(project));
     }
 
-    if todos.is_empty() {
-        print_empty_message();
-        return;
-    }
+    todos
+}
 
-    // Sort
-    let sort_by = sort_by.unwrap_or(SortBy::Priority);
+pub fn sort(todos: &mut [&Todo], sort_by: SortBy) {
     match sort_by {
         SortBy::Priority => {
             todos.sort_by(|a, b| {
@@ -57,7 +98,7 @@ pub fn run(
         SortBy::Time => {
             todos.sort_by(|a, b| {
                 match (&a.deadline, &b.deadline) {
-                    (Some(da), Some(db)) => da.cmp(db),
+                    (Some(da), Some(db)) => da.sort_key().cmp(&db.sort_key()),
                     (Some(_), None) => std::cmp::Ordering::Less,
                     (None, Some(_)) => std::cmp::Ordering::Greater,
                     (None, None) => a.id.cmp(&b.id),
@@ -65,11 +106,7 @@ pub fn run(
             });
         }
         SortBy::Created => {
-            todos.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+            todos.sort_by_key(|t| t.created_at);
         }
     }
-
-    for todo in todos {
-        println!("{}", format_todo(todo, config));
-    }
 }
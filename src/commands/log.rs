@@ -0,0 +1,285 @@
+use crate::activity::{self, Action, Activity};
+use crate::deadline::Deadline;
+use crate::display::{print_error, print_json_list, DisplayConfig};
+use crate::select::Selector;
+use crate::store::Store;
+use crate::time::{clock_format, date_format, format_duration, parse_deadline_with, to_viewer, Resolution};
+use crate::todo::{is_subproject, Change, Field, Todo};
+use chrono::{DateTime, Local, Utc};
+use colored::Colorize;
+use serde_json::json;
+
+/// Show the change history of tasks: `tsk log 3`.
+pub fn run(selector: Selector, store: &Store, config: &DisplayConfig) {
+    if selector.refs.is_empty() {
+        print_error("At least one task ID is required");
+        return;
+    }
+
+    let selection = selector.resolve(store);
+    for e in &selection.errors {
+        print_error(e);
+    }
+
+    let todos: Vec<&Todo> = selection.ids.iter().filter_map(|&id| store.get(id)).collect();
+
+    if config.is_machine() {
+        let entries: Vec<_> = todos
+            .iter()
+            .map(|t| {
+                json!({
+                    "id": t.id,
+                    "uuid": t.uuid,
+                    "text": t.text,
+                    "created_at": t.created_at,
+                    "history": t.history,
+                })
+            })
+            .collect();
+        print_json_list(&entries, config);
+        return;
+    }
+
+    for (i, todo) in todos.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_history(todo, config);
+    }
+}
+
+/// Which activity `tsk log` shows when no task is given.
+pub struct FeedFilter {
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub project: Option<String>,
+    pub actions: Vec<Action>,
+}
+
+/// Show what happened across the store, oldest first.
+pub fn feed(filter: FeedFilter, config: &DisplayConfig) {
+    let now = Utc::now();
+    let bounds = (parse_bound(filter.since.as_deref(), now), parse_bound(filter.until.as_deref(), now));
+    let (since, until) = match bounds {
+        // A bare date covers the whole day at either end of the range
+        (Ok(since), Ok(until)) => (
+            since.map(|d| d.in_viewer_zone().with_timezone(&Utc)),
+            until.map(|d| d.instant()),
+        ),
+        (Err(e), _) | (_, Err(e)) => {
+            print_error(&e);
+            return;
+        }
+    };
+
+    let entries = match activity::load() {
+        Ok(entries) => select(entries, &filter, since, until),
+        Err(e) => {
+            print_error(&format!("Could not read the activity log: {}", e));
+            return;
+        }
+    };
+
+    if config.is_machine() {
+        print_json_list(&entries, config);
+        return;
+    }
+
+    if entries.is_empty() {
+        println!("No activity.");
+        return;
+    }
+
+    for entry in &entries {
+        let time = format_time(entry.at);
+        let mut line = format!("#{} {}", entry.id, entry.text);
+        if let Some(project) = &entry.project {
+            line.push_str(&format!(" @{}", project));
+        }
+
+        if config.use_color {
+            let action = format!("{:<9}", entry.action.label());
+            let action = match entry.action {
+                Action::Added | Action::Completed => action.green(),
+                Action::Deleted | Action::Cleared => action.yellow(),
+                _ => action.cyan(),
+            };
+            println!("{}  {}  {}", time.dimmed(), action, line);
+        } else {
+            println!("{}  {:<9}  {}", time, entry.action.label(), line);
+        }
+    }
+}
+
+/// Entries between `since` and `until` with one of the filter's actions, on
+/// its project or a subproject.
+fn select(
+    entries: Vec<Activity>,
+    filter: &FeedFilter,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> Vec<Activity> {
+    let project = filter.project.as_deref().map(|p| p.trim_start_matches('@'));
+    entries
+        .into_iter()
+        .filter(|e| since.is_none_or(|since| e.at >= since))
+        .filter(|e| until.is_none_or(|until| e.at <= until))
+        .filter(|e| filter.actions.is_empty() || filter.actions.contains(&e.action))
+        .filter(|e| project.is_none_or(|p| e.project.as_deref().is_some_and(|name| is_subproject(name, p))))
+        .collect()
+}
+
+/// Read a range bound looking back, so `monday` is the last Monday (or
+/// today) rather than the next one.
+fn parse_bound(input: Option<&str>, now: DateTime<Utc>) -> Result<Option<Deadline>, String> {
+    match input {
+        Some(s) => parse_deadline_with(s, now, Resolution::Past)
+            .map(Some)
+            .ok_or_else(|| format!("Could not parse time \"{}\"", s)),
+        None => Ok(None),
+    }
+}
+
+fn print_history(todo: &Todo, config: &DisplayConfig) {
+    let header = format!("#{} {}", todo.id, todo.text);
+    if config.use_color {
+        println!("{}", header.bold());
+    } else {
+        println!("{}", header);
+    }
+
+    let mut rows = vec![(format_time(todo.created_at), "created".to_string(), String::new())];
+    for change in &todo.history {
+        let values = format!("{} → {}", format_value(change, &change.from), format_value(change, &change.to));
+        rows.push((format_time(change.at), change.field.label().to_string(), values));
+    }
+
+    let time_width = rows.iter().map(|(time, _, _)| time.len()).max().unwrap_or(0);
+    for (time, field, values) in rows {
+        let time = format!("{:<width$}", time, width = time_width);
+        let line = format!("{:<9} {}", field, values);
+        if config.use_color {
+            println!("  {}  {}", time.dimmed(), line.trim_end());
+        } else {
+            println!("  {}  {}", time, line.trim_end());
+        }
+    }
+
+    // Pushed deadlines are the usual question in a retrospective
+    let moved = todo
+        .history
+        .iter()
+        .filter(|c| c.field == Field::Deadline && c.from.is_some() && c.to.is_some())
+        .count();
+    if moved > 0 {
+        println!("  Deadline moved {} time{}", moved, if moved == 1 { "" } else { "s" });
+    }
+}
+
+fn format_time(at: DateTime<Local>) -> String {
+    to_viewer(at.with_timezone(&Utc))
+        .format(&format!("{} {}", date_format(), clock_format()))
+        .to_string()
+}
+
+/// Show a stored value the way the rest of the output does.
+fn format_value(change: &Change, value: &Option<String>) -> String {
+    let Some(value) = value else {
+        return "none".to_string();
+    };
+
+    match change.field {
+        Field::Deadline => match value.parse::<Deadline>() {
+            Ok(Deadline::Day(date)) => date.format(date_format()).to_string(),
+            Ok(deadline) => deadline
+                .in_viewer_zone()
+                .format(&format!("{} {}", date_format(), clock_format()))
+                .to_string(),
+            Err(_) => value.clone(),
+        },
+        Field::Priority => match value.as_str() {
+            "1" => "!!!".to_string(),
+            "2" => "!!".to_string(),
+            "3" => "!".to_string(),
+            _ => value.clone(),
+        },
+        Field::Estimate => value.parse().map(format_duration).unwrap_or_else(|_| value.clone()),
+        Field::Project => format!("@{}", value),
+        Field::Tags => value.split(' ').map(|t| format!("+{}", t)).collect::<Vec<_>>().join(" "),
+        Field::Text | Field::Status => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn weekday_bounds_look_back() {
+        // Monday 2026-10-19
+        let now = Utc.with_ymd_and_hms(2026, 10, 19, 12, 0, 0).unwrap();
+        for weekday in ["monday", "wednesday", "friday", "sunday"] {
+            let bound = parse_bound(Some(weekday), now).unwrap().unwrap();
+            assert!(bound.in_viewer_zone() <= now, "{} resolved to {}", weekday, bound);
+        }
+        assert!(parse_bound(Some("by the river"), now).is_err());
+        assert_eq!(parse_bound(None, now), Ok(None));
+    }
+
+    fn entry(action: Action, project: Option<&str>, day: u32) -> Activity {
+        let mut todo = Todo::new(day, format!("task {}", day));
+        todo.project = project.map(str::to_string);
+        let mut entry = Activity::new(action, &todo);
+        entry.at = Local.with_ymd_and_hms(2026, 10, day, 12, 0, 0).unwrap();
+        entry
+    }
+
+    fn entries() -> Vec<Activity> {
+        vec![
+            entry(Action::Added, Some("work"), 1),
+            entry(Action::Completed, Some("work.api"), 2),
+            entry(Action::Deleted, Some("home"), 3),
+            entry(Action::Completed, None, 4),
+        ]
+    }
+
+    fn filter(actions: Vec<Action>, project: Option<&str>) -> FeedFilter {
+        FeedFilter {
+            since: None,
+            until: None,
+            project: project.map(str::to_string),
+            actions,
+        }
+    }
+
+    fn ids(entries: &[Activity]) -> Vec<u32> {
+        entries.iter().map(|e| e.id).collect()
+    }
+
+    #[test]
+    fn filters_by_action_and_project() {
+        let all = select(entries(), &filter(Vec::new(), None), None, None);
+        assert_eq!(ids(&all), vec![1, 2, 3, 4]);
+
+        let completed = select(entries(), &filter(vec![Action::Completed], None), None, None);
+        assert_eq!(ids(&completed), vec![2, 4]);
+
+        // Subprojects count, and the `@` is optional
+        let work = select(entries(), &filter(Vec::new(), Some("@work")), None, None);
+        assert_eq!(ids(&work), vec![1, 2]);
+
+        let both = select(entries(), &filter(vec![Action::Deleted, Action::Added], Some("work")), None, None);
+        assert_eq!(ids(&both), vec![1]);
+    }
+
+    #[test]
+    fn filters_by_date_range() {
+        let at = |day| Local.with_ymd_and_hms(2026, 10, day, 12, 0, 0).unwrap().with_timezone(&Utc);
+        let ranged = select(entries(), &filter(Vec::new(), None), Some(at(2)), Some(at(3)));
+        assert_eq!(ids(&ranged), vec![2, 3]);
+
+        let since = select(entries(), &filter(Vec::new(), None), Some(at(3)), None);
+        assert_eq!(ids(&since), vec![3, 4]);
+    }
+}
@@ -1,10 +1,265 @@
 pub mod add;
 pub mod clear;
+pub mod config;
+pub mod daemon;
+pub mod defer;
 pub mod delete;
 pub mod done;
 pub mod edit;
 pub mod list;
+pub mod log;
+pub mod parse_date;
 pub mod project;
+pub mod renumber;
+pub mod reopen;
+pub mod shell;
 pub mod stats;
+pub mod tag;
 pub mod today;
+pub mod tui;
 pub mod undo;
+
+use crate::cli::{Cli, Command, ConfigAction, ProjectAction, TagAction};
+use crate::display::{print_error, DisplayConfig};
+use crate::config::Settings;
+use crate::select::Selector;
+use crate::store::Store;
+use add::AddOptions;
+use edit::EditOptions;
+use list::Filter;
+
+/// Run the subcommand parsed from one command line.
+pub fn dispatch(cli: Cli, store: &mut Store, config: &DisplayConfig, settings: &Settings) {
+    match cli.command {
+        Some(Command::Add {
+            text,
+            p,
+            t,
+            estimate,
+            remind,
+        }) => {
+            let opts = AddOptions {
+                text,
+                priority: p,
+                time: t,
+                estimate,
+                remind,
+            };
+            add::run(opts, store, config, &settings.rules);
+        }
+
+        Some(Command::Ls) | None => {
+            list::run(store, config, cli.sort_by, Filter::Open, &cli.tags, cli.project.as_deref());
+        }
+
+        Some(Command::All) => {
+            list::run(store, config, cli.sort_by, Filter::All, &cli.tags, cli.project.as_deref());
+        }
+
+        Some(Command::Today) => {
+            today::run(store, config);
+        }
+
+        Some(Command::Week) => {
+            list::run(store, config, cli.sort_by, Filter::Week, &cli.tags, cli.project.as_deref());
+        }
+
+        Some(Command::Overdue) => {
+            list::run(store, config, cli.sort_by, Filter::Overdue, &cli.tags, cli.project.as_deref());
+        }
+
+        Some(Command::Done { ids, overdue, yes }) => {
+            if let Some(selector) = selector(&ids, &cli.tags, cli.project.as_deref(), overdue) {
+                done::run(selector, yes, store, config);
+            }
+        }
+
+        Some(Command::Delete { ids, overdue, yes }) => {
+            if let Some(selector) = selector(&ids, &cli.tags, cli.project.as_deref(), overdue) {
+                delete::run(selector, yes, store, config);
+            }
+        }
+
+        Some(Command::Reopen { ids }) => {
+            if let Some(selector) = selector(&ids, &[], None, false) {
+                reopen::run(selector, store, config);
+            }
+        }
+
+        Some(Command::Edit {
+            target,
+            text,
+            p,
+            t,
+            clear_time,
+            clear_priority,
+            clear_project,
+            estimate,
+            clear_estimate,
+            remind,
+            clear_reminders,
+            overdue,
+            yes,
+        }) => {
+            let opts = EditOptions {
+                text,
+                priority: p,
+                time: t,
+                clear_time,
+                clear_priority,
+                clear_project,
+                estimate,
+                clear_estimate,
+                remind,
+                clear_reminders,
+            };
+            let specs = [target];
+            if let Some(selector) = selector(&specs, &cli.tags, cli.project.as_deref(), overdue) {
+                edit::run(selector, opts, yes, store, config, &settings.rules);
+            }
+        }
+
+        Some(Command::Project { action, name }) => match (action, name) {
+            (Some(ProjectAction::New { name, description, t, hue }), _) => {
+                project::create(&name, description, t, hue, store, config)
+            }
+            (
+                Some(ProjectAction::Edit {
+                    name,
+                    description,
+                    t,
+                    status,
+                    hue,
+                    clear_description,
+                    clear_time,
+                    clear_hue,
+                }),
+                _,
+            ) => {
+                let opts = project::ProjectEdit {
+                    description,
+                    time: t,
+                    status,
+                    color: hue,
+                    clear_description,
+                    clear_time,
+                    clear_color: clear_hue,
+                };
+                project::edit(&name, opts, store, config)
+            }
+            (Some(ProjectAction::Close { name }), _) => project::close(&name, store, config),
+            (Some(ProjectAction::Rename { old, new }), _) => project::rename(&old, &new, store, config),
+            (None, Some(name)) => project::run(store, config, &name),
+            (None, None) => print_error("Project name is required"),
+        },
+
+        Some(Command::Projects) => {
+            project::list_projects(store, config);
+        }
+
+        Some(Command::Tag { action }) => match action {
+            TagAction::Rename { old, new } => tag::rename(&old, &new, store, config),
+            TagAction::Merge { from, into } => tag::merge(&from, &into, store, config),
+        },
+
+        Some(Command::Config { action }) => match action {
+            ConfigAction::List => config::list(config),
+            ConfigAction::Get { key } => config::get(&key, config),
+            ConfigAction::Set { key, value, local } => config::set(&key, &value, local, config),
+        },
+
+        Some(Command::Tags) => {
+            tag::list_tags(store, config);
+        }
+
+        Some(Command::Snooze { args, overdue, yes }) => {
+            let (specs, when) = split_target(args, overdue);
+            if let Some(selector) = selector(&specs, &cli.tags, cli.project.as_deref(), overdue) {
+                defer::snooze(selector, &when, yes, store, config);
+            }
+        }
+
+        Some(Command::Defer {
+            args,
+            keep_time,
+            overdue,
+            yes,
+        }) => {
+            let (specs, when) = split_target(args, overdue);
+            if let Some(selector) = selector(&specs, &cli.tags, cli.project.as_deref(), overdue) {
+                defer::defer(selector, &when, keep_time, yes, store, config);
+            }
+        }
+
+        Some(Command::Clear) => {
+            clear::run(store, config);
+        }
+
+        Some(Command::Daemon { interval, once }) => {
+            daemon::run(interval, once, settings.reminder_command.as_deref(), config);
+        }
+
+        Some(Command::Renumber) => {
+            renumber::run(store, config);
+        }
+
+        Some(Command::Log {
+            ids,
+            since,
+            until,
+            action,
+        }) => {
+            if ids.is_empty() {
+                let filter = log::FeedFilter {
+                    since,
+                    until,
+                    project: cli.project.clone(),
+                    actions: action,
+                };
+                log::feed(filter, config);
+            } else if let Some(selector) = selector(&ids, &[], None, false) {
+                log::run(selector, store, config);
+            }
+        }
+
+        Some(Command::Undo) => {
+            undo::run(store, config);
+        }
+
+        Some(Command::Stats) => {
+            stats::run(store, config);
+        }
+
+        Some(Command::Tui) => {
+            tui::run(store, config);
+        }
+
+        Some(Command::Shell) => {
+            shell::run(store, config, settings);
+        }
+
+        Some(Command::ParseDate { expr }) => {
+            parse_date::run(&expr.join(" "), config);
+        }
+    }
+}
+
+/// Split `<target> <time words...>`; with `--overdue` every word is time.
+fn split_target(mut args: Vec<String>, overdue: bool) -> (Vec<String>, String) {
+    if overdue || args.len() < 2 {
+        return (Vec::new(), args.join(" "));
+    }
+    let rest = args.split_off(1);
+    (args, rest.join(" "))
+}
+
+/// Parse a bulk selector, reporting malformed ID lists.
+fn selector(specs: &[String], tags: &[String], project: Option<&str>, overdue: bool) -> Option<Selector> {
+    match Selector::parse(specs, tags, project, overdue) {
+        Ok(s) => Some(s),
+        Err(e) => {
+            print_error(&e);
+            None
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::deadline::Deadline;
+use crate::display::{print_error, print_json, DisplayConfig};
+use crate::time::{clock_format, long_date_format, parse_deadline_at, viewer_now};
+use chrono::{DateTime, SecondsFormat, Utc};
+use colored::Colorize;
+use serde_json::json;
+
+pub fn run(expr: &str, config: &DisplayConfig) {
+    let now = Utc::now();
+    let Some(deadline) = parse_deadline_at(expr, now) else {
+        print_error(&format!("Could not parse time \"{}\"", expr));
+        return;
+    };
+
+    let resolved = deadline.in_viewer_zone();
+    let (shown, relative, iso) = match deadline {
+        Deadline::Day(date) => (
+            format!("{} (all day)", resolved.format(long_date_format())),
+            describe_days((date - viewer_now().date_naive()).num_days()),
+            date.format("%Y-%m-%d").to_string(),
+        ),
+        _ => (
+            format!("{} at {}", resolved.format(long_date_format()), resolved.format(clock_format())),
+            describe_offset(deadline.instant(), now),
+            resolved.to_rfc3339_opts(SecondsFormat::Secs, false),
+        ),
+    };
+    let floating = matches!(deadline, Deadline::Floating(_));
+    let zone = deadline.zone().map(|z| z.name());
+
+    if config.is_machine() {
+        print_json(
+            &json!({
+                "input": expr,
+                "resolved": iso,
+                "relative": relative,
+                "zone": zone,
+                "floating": floating,
+                "all_day": deadline.is_all_day(),
+            }),
+            config,
+        );
+        return;
+    }
+
+    let mut rows = vec![
+        ("Input", expr.to_string()),
+        ("Resolved", shown),
+        ("ISO 8601", iso),
+        ("Relative", relative),
+    ];
+    if let Some(zone) = zone {
+        rows.push(("Zone", zone.to_string()));
+    } else if floating {
+        rows.push(("Zone", "floating (same wall time in any zone)".to_string()));
+    }
+
+    for (label, value) in rows {
+        let label = format!("{:<10}", format!("{}:", label));
+        if config.use_color {
+            println!("  {} {}", label.dimmed(), value);
+        } else {
+            println!("  {} {}", label, value);
+        }
+    }
+}
+
+/// "in 3 days", "5 hours ago", "now".
+fn describe_offset(target: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let diff = target - now;
+    let minutes = diff.num_minutes().abs();
+
+    let amount = if minutes < 1 {
+        return "now".to_string();
+    } else if minutes < 60 {
+        plural(minutes, "minute")
+    } else if minutes < 60 * 48 {
+        plural(minutes / 60, "hour")
+    } else {
+        plural(minutes / (60 * 24), "day")
+    };
+
+    if diff.num_minutes() > 0 {
+        format!("in {}", amount)
+    } else {
+        format!("{} ago", amount)
+    }
+}
+
+/// "tomorrow", "in 3 days", "2 days ago".
+fn describe_days(days: i64) -> String {
+    match days {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        -1 => "yesterday".to_string(),
+        d if d > 0 => format!("in {}", plural(d, "day")),
+        d => format!("{} ago", plural(-d, "day")),
+    }
+}
+
+fn plural(n: i64, unit: &str) -> String {
+    format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" })
+}
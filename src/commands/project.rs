@@ -1,8 +1,15 @@
-use crate::display::DisplayConfig;
+use crate::activity::{self, Action};
+use crate::display::{
+    print_error, print_json, print_json_list, print_mutation, render_rows, warn_if_past, DisplayConfig, View,
+};
+use crate::project::{Project, ProjectColor, ProjectStatus};
 use crate::store::Store;
+use crate::deadline::Deadline;
+use crate::time::{parse_deadline, viewer_now};
 use crate::todo::Todo;
 use colored::Colorize;
-use std::collections::HashMap;
+use serde_json::json;
+use std::collections::BTreeMap;
 
 pub fn run(store: &Store, config: &DisplayConfig, project_name: &str) {
     let mut todos: Vec<&Todo> = store
@@ -10,18 +17,60 @@ pub fn run(store: &Store, config: &DisplayConfig, project_name: &str) {
         .filter(|t| t.in_project(project_name))
         .collect();
 
+    // Sort by priority, then ID
+    todos.sort_by(|a, b| {
+        let pa = a.priority.unwrap_or(99);
+        let pb = b.priority.unwrap_or(99);
+        pa.cmp(&pb).then_with(|| a.id.cmp(&b.id))
+    });
+
+    if config.is_machine() {
+        print_json_list(&todos, config);
+        return;
+    }
+
     let count = todos.len();
+    let record = store.project(project_name);
 
     // Print header
     let task_word = if count == 1 { "task" } else { "tasks" };
-    let header = format!("Project: {} ({} {})", project_name, count, task_word);
+    let mut header = format!("Project: {} ({} {})", project_name, count, task_word);
+    if let Some(status) = record.map(|r| r.status).filter(|s| *s != ProjectStatus::Active) {
+        header.push_str(&format!(" [{}]", status.label()));
+    }
     let separator = "─".repeat(38);
 
     if config.use_color {
-        println!("  {}", header.bold());
-        println!("  {}", separator.dimmed());
+        let color = record.map(Project::display_color).unwrap_or(colored::Color::Magenta);
+        println!("  {}", header.color(color).bold());
     } else {
         println!("  {}", header);
+    }
+
+    if let Some(description) = record.and_then(|r| r.description.as_ref()) {
+        println!("  {}", description);
+    }
+
+    // Progress counts completed tasks too, so it moves as work gets done
+    let total = store.todos.iter().filter(|t| t.in_project(project_name)).count();
+    if total > 0 {
+        let done = total - count;
+        let mut progress = format!("{}/{} done ({}%)", done, total, done * 100 / total);
+        if let Some(target) = record.and_then(|r| r.target) {
+            progress.push_str(&format!(" · {}", days_remaining(target)));
+        }
+        if config.use_color {
+            println!("  {}", progress.dimmed());
+        } else {
+            println!("  {}", progress);
+        }
+    } else if let Some(target) = record.and_then(|r| r.target) {
+        println!("  {}", days_remaining(target));
+    }
+
+    if config.use_color {
+        println!("  {}", separator.dimmed());
+    } else {
         println!("  {}", separator);
     }
 
@@ -34,28 +83,105 @@ pub fn run(store: &Store, config: &DisplayConfig, project_name: &str) {
         return;
     }
 
-    // Sort by priority, then ID
-    todos.sort_by(|a, b| {
-        let pa = a.priority.unwrap_or(99);
-        let pb = b.priority.unwrap_or(99);
-        pa.cmp(&pb).then_with(|| a.id.cmp(&b.id))
-    });
-
+    // Tasks directly in the project first, then one section per subproject
+    let mut direct: Vec<&Todo> = Vec::new();
+    let mut subprojects: BTreeMap<Vec<String>, (String, Vec<&Todo>)> = BTreeMap::new();
     for todo in todos {
-        println!("{}", format_project_todo(todo, config));
+        let name = todo.project.as_deref().unwrap_or_default();
+        if name.eq_ignore_ascii_case(project_name) {
+            direct.push(todo);
+        } else {
+            subprojects
+                .entry(path_key(name))
+                .or_insert_with(|| (name.to_string(), Vec::new()))
+                .1
+                .push(todo);
+        }
+    }
+
+    for row in render_rows(&direct, View::Project, config) {
+        println!("{}", row);
+    }
+
+    for (name, todos) in subprojects.values() {
+        println!();
+        let header = format!("{} ({})", name, todos.len());
+        if config.use_color {
+            println!("  {}", header.magenta().bold());
+        } else {
+            println!("  {}", header);
+        }
+        for row in render_rows(todos, View::Project, config) {
+            println!("{}", row);
+        }
     }
 }
 
+/// A node in the dotted project tree.
+struct ProjectNode {
+    name: String,
+    depth: usize,
+    direct: usize,
+}
+
 pub fn list_projects(store: &Store, config: &DisplayConfig) {
-    let mut project_counts: HashMap<String, usize> = HashMap::new();
+    // Keyed by lowercase path segments so children sort right after their parent
+    let mut nodes: BTreeMap<Vec<String>, ProjectNode> = BTreeMap::new();
 
     for todo in store.open_todos() {
-        if let Some(ref project) = todo.project {
-            *project_counts.entry(project.clone()).or_insert(0) += 1;
+        let Some(ref project) = todo.project else {
+            continue;
+        };
+
+        // Make sure every ancestor exists, even without tasks of its own
+        let segments: Vec<&str> = project.split('.').collect();
+        for depth in 1..=segments.len() {
+            let name = segments[..depth].join(".");
+            nodes.entry(path_key(&name)).or_insert(ProjectNode { name, depth, direct: 0 });
+        }
+        if let Some(node) = nodes.get_mut(&path_key(project)) {
+            node.direct += 1;
         }
     }
 
-    if project_counts.is_empty() {
+    // Recorded projects stay listed after their last task is done
+    for record in &store.projects {
+        let segments: Vec<&str> = record.name.split('.').collect();
+        for depth in 1..=segments.len() {
+            let name = segments[..depth].join(".");
+            nodes.entry(path_key(&name)).or_insert(ProjectNode { name, depth, direct: 0 });
+        }
+    }
+
+    // Roll each node's count up from its descendants
+    let projects: Vec<(&ProjectNode, usize)> = nodes
+        .iter()
+        .map(|(key, node)| {
+            let total = nodes.range(key.clone()..).take_while(|(k, _)| k.starts_with(key)).map(|(_, n)| n.direct).sum();
+            (node, total)
+        })
+        .collect();
+
+    if config.is_machine() {
+        let rows: Vec<_> = projects
+            .iter()
+            .map(|(node, count)| {
+                let record = store.project(&node.name);
+                json!({
+                    "name": node.name,
+                    "open": count,
+                    "status": record.map(|r| r.status),
+                    "description": record.and_then(|r| r.description.as_ref()),
+                    "target": record.and_then(|r| r.target),
+                    "color": record.and_then(|r| r.color),
+                })
+            })
+            .collect();
+        print_json_list(&rows, config);
+        return;
+    }
+
+    if projects.is_empty() {
         if config.use_color {
             println!("{}", "No projects found.".dimmed());
         } else {
@@ -64,82 +190,242 @@ pub fn list_projects(store: &Store, config: &DisplayConfig) {
         return;
     }
 
-    // Sort alphabetically
-    let mut projects: Vec<_> = project_counts.into_iter().collect();
-    projects.sort_by(|a, b| a.0.to_lowercase().cmp(&b.0.to_lowercase()));
-
-    for (project, count) in projects {
+    for (node, count) in projects {
+        let indent = "  ".repeat(node.depth);
+        let label = node.name.rsplit('.').next().unwrap_or(&node.name);
         let task_word = if count == 1 { "task" } else { "tasks" };
+        let record = store.project(&node.name);
+        let status = match record.map(|r| r.status) {
+            Some(status) if status != ProjectStatus::Active => format!(" [{}]", status.label()),
+            _ => String::new(),
+        };
         if config.use_color {
-            println!("  {} ({} {})", project.magenta(), count, task_word);
+            let color = record.map(Project::display_color).unwrap_or(colored::Color::Magenta);
+            println!("{}{} ({} {}){}", indent, label.color(color), count, task_word, status.dimmed());
         } else {
-            println!("  {} ({} {})", project, count, task_word);
+            println!("{}{} ({} {}){}", indent, label, count, task_word, status);
         }
     }
 }
 
-fn format_project_todo(todo: &Todo, config: &DisplayConfig) -> String {
-    let id = format!("{:>4}", todo.id);
-    let priority = todo.priority_display();
-    let text = truncate(&todo.text, 35);
+/// "12 days left", "due today" or "3 days overdue".
+fn days_remaining(target: Deadline) -> String {
+    let days = (target.in_viewer_zone().date_naive() - viewer_now().date_naive()).num_days();
+    match days {
+        0 => "due today".to_string(),
+        1 => "1 day left".to_string(),
+        -1 => "1 day overdue".to_string(),
+        d if d > 0 => format!("{} days left", d),
+        d => format!("{} days overdue", -d),
+    }
+}
+
+fn path_key(project: &str) -> Vec<String> {
+    project.split('.').map(str::to_lowercase).collect()
+}
+
+/// Move every task in `old` to `new`. If `new` already has tasks the two
+/// projects are merged.
+pub fn rename(old: &str, new: &str, store: &mut Store, config: &DisplayConfig) {
+    let (old, new) = (old.trim_start_matches('@'), new.trim_start_matches('@'));
+    if new.is_empty() {
+        print_error("New project name cannot be empty");
+        return;
+    }
+
+    if !store.has_project(old) {
+        print_error(&format!("No tasks in project @{}", old));
+        return;
+    }
+
+    let merging = !old.eq_ignore_ascii_case(new) && store.has_project(new);
+    let ids = store.rename_project(old, new);
+
+    if let Err(e) = store.save_with_undo() {
+        print_error(&format!("Could not save: {}", e));
+        return;
+    }
+
+    let changed: Vec<Todo> = store.todos.iter().filter(|t| ids.contains(&t.id)).cloned().collect();
+    activity::record(Action::Edited, &changed);
+
+    if config.is_machine() {
+        print_mutation(if merging { "merged" } else { "renamed" }, &changed, config);
+        return;
+    }
 
-    let deadline_str = if let Some(deadline) = todo.deadline {
-        crate::time::format_deadline(deadline, todo.is_overdue())
+    let count = ids.len();
+    let msg = format!(
+        "{} @{} → @{} ({} task{})",
+        if merging { "Merged" } else { "Renamed" },
+        old,
+        new,
+        count,
+        if count == 1 { "" } else { "s" }
+    );
+    if config.use_color {
+        println!("{}", msg.green());
     } else {
-        "—".to_string()
+        println!("{}", msg);
+    }
+}
+
+/// Changes requested by `tsk project edit`.
+pub struct ProjectEdit {
+    pub description: Option<String>,
+    pub time: Option<String>,
+    pub status: Option<ProjectStatus>,
+    pub color: Option<ProjectColor>,
+    pub clear_description: bool,
+    pub clear_time: bool,
+    pub clear_color: bool,
+}
+
+pub fn create(
+    name: &str,
+    description: Option<String>,
+    time: Option<String>,
+    color: Option<ProjectColor>,
+    store: &mut Store,
+    config: &DisplayConfig,
+) {
+    let name = name.trim_start_matches('@');
+    if name.is_empty() {
+        print_error("Project name cannot be empty");
+        return;
+    }
+
+    if let Some(existing) = store.project(name) {
+        print_error(&format!("Project @{} already exists", existing.name));
+        return;
+    }
+
+    let target = match time {
+        Some(t) => match parse_deadline(&t) {
+            Some(dt) => Some(dt),
+            None => {
+                print_error(&format!("Could not parse time \"{}\"", t));
+                return;
+            }
+        },
+        None => None,
     };
 
-    let tags_str = if todo.tags.is_empty() {
-        String::new()
-    } else {
-        todo.tags
-            .iter()
-            .map(|t| format!("+{}", t))
-            .collect::<Vec<_>>()
-            .join(" ")
+    let project = Project::new(name.to_string())
+        .with_description(description)
+        .with_target(target)
+        .with_color(color);
+    store.projects.push(project.clone());
+
+    if let Err(e) = store.save_with_undo() {
+        print_error(&format!("Could not save: {}", e));
+        return;
+    }
+
+    print_project_change("created", "Created", &project, config);
+    warn_if_past(project.target);
+}
+
+pub fn edit(name: &str, opts: ProjectEdit, store: &mut Store, config: &DisplayConfig) {
+    let name = name.trim_start_matches('@');
+
+    let target = match opts.time {
+        Some(t) if !opts.clear_time => match parse_deadline(&t) {
+            Some(dt) => Some(dt),
+            None => {
+                print_error(&format!("Could not parse time \"{}\"", t));
+                return;
+            }
+        },
+        _ => None,
     };
 
-    if config.use_color {
-        let id_colored = id.dimmed();
+    // Tasks may already use the name; editing gives it a record
+    if store.project(name).is_none() {
+        if !store.has_project(name) {
+            print_error(&format!("Project @{} not found", name));
+            return;
+        }
+        store.projects.push(Project::new(name.to_string()));
+    }
 
-        let priority_colored = match todo.priority {
-            Some(1) => "!!!".red().bold(),
-            Some(2) => "!! ".yellow(),
-            Some(3) => "!  ".blue(),
-            _ => "   ".normal(),
-        };
+    let Some(project) = store.project_mut(name) else {
+        return;
+    };
 
-        let deadline_colored = if todo.is_overdue() {
-            deadline_str.red().bold()
-        } else if todo
-            .deadline
-            .map(crate::time::is_due_today)
-            .unwrap_or(false)
-        {
-            deadline_str.yellow()
-        } else {
-            deadline_str.normal()
-        };
+    if opts.clear_description {
+        project.description = None;
+    } else if opts.description.is_some() {
+        project.description = opts.description;
+    }
+
+    if opts.clear_time {
+        project.target = None;
+    } else if target.is_some() {
+        project.target = target;
+    }
 
-        let tags_colored = tags_str.cyan();
+    if opts.clear_color {
+        project.color = None;
+    } else if opts.color.is_some() {
+        project.color = opts.color;
+    }
 
-        format!(
-            "  {} {}  {:<35}  {:<18}  {}",
-            id_colored, priority_colored, text, deadline_colored, tags_colored
-        )
-    } else {
-        format!(
-            "  {} {}  {:<35}  {:<18}  {}",
-            id, priority, text, deadline_str, tags_str
-        )
+    if let Some(status) = opts.status {
+        project.status = status;
     }
+
+    let project = project.clone();
+    if let Err(e) = store.save_with_undo() {
+        print_error(&format!("Could not save: {}", e));
+        return;
+    }
+
+    print_project_change("updated", "Updated", &project, config);
+    warn_if_past(target);
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.chars().count() <= max_len {
-        s.to_string()
+pub fn close(name: &str, store: &mut Store, config: &DisplayConfig) {
+    let name = name.trim_start_matches('@');
+    edit(
+        name,
+        ProjectEdit {
+            description: None,
+            time: None,
+            status: Some(ProjectStatus::Done),
+            color: None,
+            clear_description: false,
+            clear_time: false,
+            clear_color: false,
+        },
+        store,
+        config,
+    );
+
+    let open = store.open_todos().filter(|t| t.in_project(name)).count();
+    if open > 0 && store.project(name).is_some() && !config.is_machine() {
+        let msg = format!("Note: @{} still has {} open task{}", name, open, if open == 1 { "" } else { "s" });
+        if config.use_color {
+            println!("{}", msg.yellow());
+        } else {
+            println!("{}", msg);
+        }
+    }
+}
+
+fn print_project_change(action: &str, verb: &str, project: &Project, config: &DisplayConfig) {
+    if config.is_machine() {
+        print_json(&json!({ "status": "ok", "action": action, "project": project }), config);
+        return;
+    }
+
+    let mut msg = format!("{} project @{}", verb, project.name);
+    if project.status != ProjectStatus::Active {
+        msg.push_str(&format!(" [{}]", project.status.label()));
+    }
+    if config.use_color {
+        println!("{}", msg.green());
     } else {
-        let truncated: String = s.chars().take(max_len - 1).collect();
-        format!("{}...", truncated)
+        println!("{}", msg);
     }
 }
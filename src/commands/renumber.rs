@@ -0,0 +1,43 @@
+use crate::display::{print_error, print_mutation, DisplayConfig};
+use crate::store::Store;
+use colored::Colorize;
+
+pub fn run(store: &mut Store, config: &DisplayConfig) {
+    let changes = store.renumber();
+
+    if !changes.is_empty() {
+        if let Err(e) = store.save_with_undo() {
+            print_error(&format!("Could not save: {}", e));
+            return;
+        }
+    }
+
+    if config.is_machine() {
+        let moved: Vec<_> = changes
+            .iter()
+            .filter_map(|(_, new)| store.todos.iter().find(|t| t.id == *new).cloned())
+            .collect();
+        print_mutation("renumbered", &moved, config);
+        return;
+    }
+
+    if changes.is_empty() {
+        println!("Task IDs are already compact.");
+        return;
+    }
+
+    for (old, new) in &changes {
+        println!("#{} → #{}", old, new);
+    }
+
+    let msg = format!(
+        "Renumbered {} task{}.",
+        changes.len(),
+        if changes.len() == 1 { "" } else { "s" }
+    );
+    if config.use_color {
+        println!("{}", msg.green());
+    } else {
+        println!("{}", msg);
+    }
+}
@@ -0,0 +1,40 @@
+use crate::activity::{self, Action};
+use crate::display::{print_error, print_mutation, print_todo_reopened, DisplayConfig};
+use crate::select::Selector;
+use crate::store::Store;
+
+pub fn run(selector: Selector, store: &mut Store, config: &DisplayConfig) {
+    if selector.refs.is_empty() {
+        print_error("At least one task ID is required");
+        return;
+    }
+
+    let selection = selector.resolve(store);
+    if !selection.report() {
+        return;
+    }
+
+    let mut reopened = Vec::new();
+
+    for id in selection.ids {
+        if let Some(todo) = store.get_mut(id) {
+            if todo.done {
+                todo.reopen();
+                print_todo_reopened(todo, config);
+                reopened.push(todo.clone());
+            } else {
+                print_error(&format!("Task #{} is not completed", id));
+            }
+        }
+    }
+
+    if !reopened.is_empty() {
+        if let Err(e) = store.save_with_undo() {
+            print_error(&format!("Could not save: {}", e));
+            return;
+        }
+        activity::record(Action::Reopened, &reopened);
+    }
+
+    print_mutation("reopened", &reopened, config);
+}
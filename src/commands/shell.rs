@@ -0,0 +1,206 @@
+use crate::alias::Aliases;
+use crate::cli::{Cli, Command};
+use crate::commands::dispatch;
+use crate::config::{ColorMode, Settings};
+use crate::display::{print_error, DisplayConfig};
+use crate::store::Store;
+use clap::{CommandFactory, Parser};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::collections::BTreeSet;
+
+/// Completes subcommands and aliases, task IDs, `+tags` and `@projects`.
+struct ShellHelper {
+    commands: Vec<String>,
+    ids: Vec<(u32, String)>,
+    tags: Vec<String>,
+    projects: Vec<String>,
+}
+
+impl ShellHelper {
+    fn new(aliases: &Aliases) -> Self {
+        let mut commands: Vec<String> = Cli::command()
+            .get_subcommands()
+            .flat_map(|c| std::iter::once(c.get_name()).chain(c.get_all_aliases()))
+            .map(str::to_string)
+            .collect();
+        commands.extend(aliases.names().cloned());
+        commands.extend(["exit".to_string(), "quit".to_string()]);
+        commands.sort();
+
+        Self {
+            commands,
+            ids: Vec::new(),
+            tags: Vec::new(),
+            projects: Vec::new(),
+        }
+    }
+
+    /// Pick up tasks, tags and projects changed by the last command.
+    fn refresh(&mut self, store: &Store) {
+        self.ids = store.todos.iter().map(|t| (t.id, t.text.clone())).collect();
+
+        let tags: BTreeSet<&String> = store.todos.iter().flat_map(|t| &t.tags).collect();
+        self.tags = tags.into_iter().cloned().collect();
+
+        let projects: BTreeSet<&String> = store.todos.iter().filter_map(|t| t.project.as_ref()).collect();
+        self.projects = projects.into_iter().cloned().collect();
+    }
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let start = line[..pos].rfind(char::is_whitespace).map(|i| i + 1).unwrap_or(0);
+        let word = &line[start..pos];
+
+        let matching = |sigil: &str, names: &[String], prefix: &str| -> Vec<Pair> {
+            names
+                .iter()
+                .filter(|n| n.to_lowercase().starts_with(&prefix.to_lowercase()))
+                .map(|n| Pair {
+                    display: format!("{}{}", sigil, n),
+                    replacement: format!("{}{}", sigil, n),
+                })
+                .collect()
+        };
+
+        let candidates = if start == 0 {
+            matching("", &self.commands, word)
+        } else if let Some(prefix) = word.strip_prefix('+') {
+            matching("+", &self.tags, prefix)
+        } else if let Some(prefix) = word.strip_prefix('@') {
+            matching("@", &self.projects, prefix)
+        } else if word.chars().all(|c| c.is_ascii_digit()) {
+            self.ids
+                .iter()
+                .filter(|(id, _)| id.to_string().starts_with(word))
+                .map(|(id, text)| Pair {
+                    display: format!("{:>3}  {}", id, text),
+                    replacement: id.to_string(),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+pub fn run(store: &mut Store, config: &DisplayConfig, settings: &Settings) {
+    let mut editor: Editor<ShellHelper, DefaultHistory> = match Editor::new() {
+        Ok(e) => e,
+        Err(e) => {
+            print_error(&format!("Could not start shell: {}", e));
+            return;
+        }
+    };
+    // Lines without their own flags keep the ones the shell was started with
+    let mut display = settings.display;
+    display.color = if config.use_color { ColorMode::Always } else { ColorMode::Never };
+    display.wrap |= config.wrap;
+    editor.set_helper(Some(ShellHelper::new(&settings.aliases)));
+
+    let history = Store::data_dir().map(|dir| dir.join("shell_history")).ok();
+    if let Some(ref path) = history {
+        let _ = editor.load_history(path);
+    }
+
+    loop {
+        if let Some(helper) = editor.helper_mut() {
+            helper.refresh(store);
+        }
+
+        let line = match editor.readline("tsk> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => {
+                print_error(&format!("Could not read input: {}", e));
+                break;
+            }
+        };
+
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line);
+
+        if matches!(line, "exit" | "quit") {
+            break;
+        }
+
+        let Some(mut args) = shlex::split(line) else {
+            print_error("Unbalanced quotes");
+            continue;
+        };
+
+        // Accept lines pasted with the program name
+        if args.first().map(String::as_str) == Some("tsk") {
+            args.remove(0);
+        }
+
+        let runs = match settings.aliases.expand(&args) {
+            Ok(runs) => runs,
+            Err(e) => {
+                print_error(&e);
+                continue;
+            }
+        };
+
+        let parsed: Result<Vec<Cli>, _> = runs
+            .into_iter()
+            .map(|args| Cli::try_parse_from(std::iter::once("tsk".to_string()).chain(args)))
+            .collect();
+        let clis = match parsed {
+            Ok(clis) => clis,
+            Err(e) => {
+                let _ = e.print();
+                continue;
+            }
+        };
+
+        if clis.iter().any(|cli| matches!(cli.command, Some(Command::Shell))) {
+            print_error("Already in the shell");
+            continue;
+        }
+
+        // Flags on the line (`--json`, `--columns`, ...) apply to that line only
+        for mut cli in clis {
+            cli.sort_by = cli.sort_by.or(settings.list.sort);
+            let line_config = display
+                .display_config(&cli, &settings.layouts, None)
+                .with_confirm_threshold(settings.bulk.confirm_threshold);
+            let layout_override = match cli.get_layout() {
+                Ok(layout) => layout,
+                Err(e) => {
+                    print_error(&format!("Invalid template: {}", e));
+                    break;
+                }
+            };
+            dispatch(cli, store, &line_config.with_layout_override(layout_override), settings);
+        }
+    }
+
+    if let Some(ref path) = history {
+        let _ = editor.save_history(path);
+    }
+}
@@ -1,7 +1,8 @@
-use crate::display::DisplayConfig;
+use crate::display::{print_json, truncate, DisplayConfig};
 use crate::store::Store;
 use chrono::{Duration, Local};
 use colored::Colorize;
+use serde_json::json;
 use std::collections::HashMap;
 
 pub fn run(store: &Store, config: &DisplayConfig) {
@@ -27,20 +28,18 @@ pub fn run(store: &Store, config: &DisplayConfig) {
         .count();
 
     // Find oldest open task
-    let oldest = store
-        .open_todos()
-        .min_by_key(|t| t.created_at)
-        .map(|t| {
-            let age = now - t.created_at;
-            let age_str = if age.num_days() > 0 {
-                format!("{} days", age.num_days())
-            } else if age.num_hours() > 0 {
-                format!("{} hours", age.num_hours())
-            } else {
-                "just now".to_string()
-            };
-            format!("{} (\"{}\")", age_str, truncate(&t.text, 20))
-        });
+    let oldest_todo = store.open_todos().min_by_key(|t| t.created_at);
+    let oldest = oldest_todo.map(|t| {
+        let age = now - t.created_at;
+        let age_str = if age.num_days() > 0 {
+            format!("{} days", age.num_days())
+        } else if age.num_hours() > 0 {
+            format!("{} hours", age.num_hours())
+        } else {
+            "just now".to_string()
+        };
+        format!("{} (\"{}\")", age_str, truncate(&t.text, 20))
+    });
 
     // Find most common tag
     let mut tag_counts: HashMap<&str, usize> = HashMap::new();
@@ -49,10 +48,23 @@ pub fn run(store: &Store, config: &DisplayConfig) {
             *tag_counts.entry(tag.as_str()).or_insert(0) += 1;
         }
     }
-    let top_tag = tag_counts
-        .into_iter()
-        .max_by_key(|(_, count)| *count)
-        .map(|(tag, count)| format!("+{} ({} tasks)", tag, count));
+    let top = tag_counts.into_iter().max_by_key(|(_, count)| *count);
+    let top_tag = top.map(|(tag, count)| format!("+{} ({} tasks)", tag, count));
+
+    if config.is_machine() {
+        print_json(
+            &json!({
+                "open": open_count,
+                "completed": completed_count,
+                "done_today": done_today,
+                "done_week": done_week,
+                "oldest": oldest_todo,
+                "top_tag": top.map(|(tag, count)| json!({ "tag": tag, "count": count })),
+            }),
+            config,
+        );
+        return;
+    }
 
     // Print stats
     if config.use_color {
@@ -79,12 +91,3 @@ pub fn run(store: &Store, config: &DisplayConfig) {
         }
     }
 }
-
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.chars().count() <= max_len {
-        s.to_string()
-    } else {
-        let truncated: String = s.chars().take(max_len - 1).collect();
-        format!("{}…", truncated)
-    }
-}
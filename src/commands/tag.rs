@@ -0,0 +1,112 @@
+use crate::activity::{self, Action};
+use crate::display::{print_error, print_json_list, print_mutation, DisplayConfig};
+use crate::store::Store;
+use crate::todo::Todo;
+use colored::Colorize;
+use serde_json::json;
+use std::collections::HashMap;
+
+pub fn rename(old: &str, new: &str, store: &mut Store, config: &DisplayConfig) {
+    let (old, new) = (old.trim_start_matches('+'), new.trim_start_matches('+'));
+    if new.is_empty() {
+        print_error("New tag name cannot be empty");
+        return;
+    }
+
+    // Renaming onto a different existing tag is a merge; make that explicit
+    if !old.eq_ignore_ascii_case(new) && store.has_tag(new) {
+        print_error(&format!("Tag +{} already exists; use `tsk tag merge {} {}`", new, old, new));
+        return;
+    }
+
+    apply("renamed", "Renamed", old, new, store, config);
+}
+
+pub fn merge(from: &str, into: &str, store: &mut Store, config: &DisplayConfig) {
+    let (from, into) = (from.trim_start_matches('+'), into.trim_start_matches('+'));
+    if into.is_empty() {
+        print_error("Target tag name cannot be empty");
+        return;
+    }
+
+    apply("merged", "Merged", from, into, store, config);
+}
+
+fn apply(action: &str, verb: &str, from: &str, to: &str, store: &mut Store, config: &DisplayConfig) {
+    if !store.has_tag(from) {
+        print_error(&format!("No tasks tagged +{}", from));
+        return;
+    }
+
+    let ids = store.rename_tag(from, to);
+
+    if let Err(e) = store.save_with_undo() {
+        print_error(&format!("Could not save: {}", e));
+        return;
+    }
+
+    let changed: Vec<Todo> = store.todos.iter().filter(|t| ids.contains(&t.id)).cloned().collect();
+    activity::record(Action::Edited, &changed);
+
+    if config.is_machine() {
+        print_mutation(action, &changed, config);
+        return;
+    }
+
+    let count = ids.len();
+    let msg = format!(
+        "{} +{} → +{} ({} task{})",
+        verb,
+        from,
+        to,
+        count,
+        if count == 1 { "" } else { "s" }
+    );
+    if config.use_color {
+        println!("{}", msg.green());
+    } else {
+        println!("{}", msg);
+    }
+}
+
+pub fn list_tags(store: &Store, config: &DisplayConfig) {
+    // Group case-insensitively, keeping the first spelling seen
+    let mut tag_counts: HashMap<String, (String, usize)> = HashMap::new();
+
+    for todo in store.open_todos() {
+        for tag in &todo.tags {
+            tag_counts.entry(tag.to_lowercase()).or_insert_with(|| (tag.clone(), 0)).1 += 1;
+        }
+    }
+
+    // Most used first, then alphabetically
+    let mut tags: Vec<_> = tag_counts.into_values().collect();
+    tags.sort_by(|(a, ca), (b, cb)| cb.cmp(ca).then_with(|| a.to_lowercase().cmp(&b.to_lowercase())));
+
+    if config.is_machine() {
+        let rows: Vec<_> = tags
+            .iter()
+            .map(|(name, count)| json!({ "name": name, "open": count }))
+            .collect();
+        print_json_list(&rows, config);
+        return;
+    }
+
+    if tags.is_empty() {
+        if config.use_color {
+            println!("{}", "No tags found.".dimmed());
+        } else {
+            println!("No tags found.");
+        }
+        return;
+    }
+
+    for (tag, count) in tags {
+        let task_word = if count == 1 { "task" } else { "tasks" };
+        if config.use_color {
+            println!("  {} ({} {})", format!("+{}", tag).cyan(), count, task_word);
+        } else {
+            println!("  +{} ({} {})", tag, count, task_word);
+        }
+    }
+}
@@ -1,9 +1,10 @@
-use crate::display::DisplayConfig;
+use crate::display::{print_json, print_json_list, render_rows, DisplayConfig, OutputFormat, View};
 use crate::store::Store;
-use crate::time::{format_deadline, is_due_today};
+use crate::time::is_due_today;
 use crate::todo::Todo;
 use chrono::Local;
 use colored::Colorize;
+use serde_json::json;
 
 pub fn run(store: &Store, config: &DisplayConfig) {
     let now = Local::now();
@@ -42,6 +43,31 @@ pub fn run(store: &Store, config: &DisplayConfig) {
     today_tasks.sort_by(sort_fn);
     high_priority_no_deadline.sort_by(sort_fn);
 
+    if config.format == OutputFormat::Jsonl {
+        let all: Vec<&Todo> = overdue
+            .iter()
+            .chain(&high_priority_today)
+            .chain(&today_tasks)
+            .chain(&high_priority_no_deadline)
+            .copied()
+            .collect();
+        print_json_list(&all, config);
+        return;
+    }
+
+    if config.is_machine() {
+        print_json(
+            &json!({
+                "overdue": overdue,
+                "high_priority_today": high_priority_today,
+                "today": today_tasks,
+                "high_priority_no_deadline": high_priority_no_deadline,
+            }),
+            config,
+        );
+        return;
+    }
+
     // Check if there's anything to show
     let total = overdue.len() + high_priority_today.len() + today_tasks.len() + high_priority_no_deadline.len();
 
@@ -129,94 +155,7 @@ fn print_section(title: &str, todos: &[&Todo], config: &DisplayConfig, style: Se
         println!("  {}", header);
     }
 
-    for todo in todos {
-        println!("{}", format_today_todo(todo, config));
-    }
-}
-
-fn format_today_todo(todo: &Todo, config: &DisplayConfig) -> String {
-    let id = format!("{:>4}", todo.id);
-    let priority = todo.priority_display();
-    let text = truncate(&todo.text, 35);
-
-    let deadline_str = if let Some(deadline) = todo.deadline {
-        format_deadline(deadline, todo.is_overdue())
-    } else {
-        "—".to_string()
-    };
-
-    let project_str = todo
-        .project
-        .as_ref()
-        .map(|p| format!("@{}", p))
-        .unwrap_or_default();
-
-    let tags_str = if todo.tags.is_empty() {
-        String::new()
-    } else {
-        todo.tags.iter().map(|t| format!("+{}", t)).collect::<Vec<_>>().join(" ")
-    };
-
-    // Combine project and tags
-    let metadata_str = if project_str.is_empty() && tags_str.is_empty() {
-        String::new()
-    } else if project_str.is_empty() {
-        tags_str.clone()
-    } else if tags_str.is_empty() {
-        project_str.clone()
-    } else {
-        format!("{} {}", project_str, tags_str)
-    };
-
-    if config.use_color {
-        let id_colored = id.dimmed();
-
-        let priority_colored = match todo.priority {
-            Some(1) => "!!!".red().bold(),
-            Some(2) => "!! ".yellow(),
-            Some(3) => "!  ".blue(),
-            _ => "   ".normal(),
-        };
-
-        let deadline_colored = if todo.is_overdue() {
-            deadline_str.red().bold()
-        } else if todo.deadline.map(is_due_today).unwrap_or(false) {
-            deadline_str.yellow()
-        } else {
-            deadline_str.normal()
-        };
-
-        let project_colored = project_str.magenta();
-        let tags_colored = tags_str.cyan();
-
-        // Combine project and tags with colors
-        let metadata_colored = if project_str.is_empty() && tags_str.is_empty() {
-            String::new()
-        } else if project_str.is_empty() {
-            format!("{}", tags_colored)
-        } else if tags_str.is_empty() {
-            format!("{}", project_colored)
-        } else {
-            format!("{} {}", project_colored, tags_colored)
-        };
-
-        format!(
-            "  {} {}  {:<35}  {:<18}  {}",
-            id_colored, priority_colored, text, deadline_colored, metadata_colored
-        )
-    } else {
-        format!(
-            "  {} {}  {:<35}  {:<18}  {}",
-            id, priority, text, deadline_str, metadata_str
-        )
-    }
-}
-
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.chars().count() <= max_len {
-        s.to_string()
-    } else {
-        let truncated: String = s.chars().take(max_len - 1).collect();
-        format!("{}…", truncated)
+    for row in render_rows(todos, View::Today, config) {
+        println!("{}", row);
     }
 }
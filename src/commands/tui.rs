@@ -0,0 +1,345 @@
+use crate::activity::{self, Action, Activity};
+use crate::cli::SortBy;
+use crate::commands::list::{self, Filter};
+use crate::display::{print_error, render_rows, DisplayConfig, View};
+use crate::store::Store;
+use crate::time::parse_deadline;
+use chrono::Utc;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, execute, queue};
+use std::io::{self, Write};
+
+const HELP: &str = "j/k move  x done/reopen  e edit  1-3/0 priority  t due  / filter  tab view  u undo  q quit";
+
+/// Views cycled with Tab.
+const VIEWS: [(Filter, &str); 4] = [
+    (Filter::Open, "Open"),
+    (Filter::Today, "Today"),
+    (Filter::Week, "Week"),
+    (Filter::All, "All"),
+];
+
+enum Prompt {
+    Text(u32),
+    Deadline(u32),
+    Filter,
+}
+
+impl Prompt {
+    fn label(&self) -> &'static str {
+        match self {
+            Prompt::Text(_) => "Text: ",
+            Prompt::Deadline(_) => "Due (empty clears): ",
+            Prompt::Filter => "Filter (+tag or @project, empty clears): ",
+        }
+    }
+}
+
+struct App {
+    view: usize,
+    tag: Option<String>,
+    project: Option<String>,
+    selected: usize,
+    scroll: usize,
+    prompt: Option<(Prompt, String)>,
+    message: Option<String>,
+}
+
+/// Restores the terminal when the TUI exits, including on panic.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+pub fn run(store: &mut Store, config: &DisplayConfig) {
+    if config.is_machine() {
+        print_error("The TUI does not support --json or --jsonl");
+        return;
+    }
+
+    let guard = match TerminalGuard::enter() {
+        Ok(g) => g,
+        Err(e) => {
+            print_error(&format!("Could not start TUI: {}", e));
+            return;
+        }
+    };
+
+    let mut app = App {
+        view: 0,
+        tag: None,
+        project: None,
+        selected: 0,
+        scroll: 0,
+        prompt: None,
+        message: None,
+    };
+
+    let result = event_loop(&mut app, store, config);
+    drop(guard);
+
+    if let Err(e) = result {
+        print_error(&format!("TUI error: {}", e));
+    }
+}
+
+fn event_loop(app: &mut App, store: &mut Store, config: &DisplayConfig) -> io::Result<()> {
+    loop {
+        draw(app, store, config)?;
+
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+
+        if app.prompt.is_some() {
+            handle_prompt_key(app, store, key);
+        } else if !handle_key(app, store, key) {
+            return Ok(());
+        }
+    }
+}
+
+/// IDs of the tasks currently shown, in display order.
+fn visible_ids(app: &App, store: &Store) -> Vec<u32> {
+    let tags: Vec<String> = app.tag.iter().cloned().collect();
+    let mut todos = list::select(store, VIEWS[app.view].0, &tags, app.project.as_deref());
+    list::sort(&mut todos, SortBy::Priority);
+    todos.iter().map(|t| t.id).collect()
+}
+
+/// Handle a key in normal mode. Returns false to quit.
+fn handle_key(app: &mut App, store: &mut Store, key: KeyEvent) -> bool {
+    let ids = visible_ids(app, store);
+    let current = ids.get(app.selected).copied();
+    app.message = None;
+
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => return false,
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+        KeyCode::Char('j') | KeyCode::Down if app.selected + 1 < ids.len() => {
+            app.selected += 1;
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.selected = app.selected.saturating_sub(1);
+        }
+        KeyCode::Char('g') | KeyCode::Home => app.selected = 0,
+        KeyCode::Char('G') | KeyCode::End => app.selected = ids.len().saturating_sub(1),
+        KeyCode::Tab => {
+            app.view = (app.view + 1) % VIEWS.len();
+            app.selected = 0;
+        }
+        KeyCode::BackTab => {
+            app.view = (app.view + VIEWS.len() - 1) % VIEWS.len();
+            app.selected = 0;
+        }
+        KeyCode::Char('/') => app.prompt = Some((Prompt::Filter, String::new())),
+        KeyCode::Char('u') => match Store::undo() {
+            Ok(restored) => {
+                *store = restored;
+                app.message = Some("Undid last change".to_string());
+            }
+            Err(_) => app.message = Some("Nothing to undo".to_string()),
+        },
+        KeyCode::Char('x') | KeyCode::Char(' ') => {
+            if let Some(todo) = current.and_then(|id| store.get_mut(id)) {
+                if todo.done {
+                    todo.reopen();
+                    app.message = Some(format!("Reopened #{}", todo.id));
+                } else {
+                    todo.mark_done();
+                    app.message = Some(format!("Completed #{}", todo.id));
+                }
+                let (id, action) = (todo.id, if todo.done { Action::Completed } else { Action::Reopened });
+                save(app, store, action, id);
+            }
+        }
+        KeyCode::Char('e') => {
+            if let Some(todo) = current.and_then(|id| store.get_mut(id)) {
+                app.prompt = Some((Prompt::Text(todo.id), todo.text.clone()));
+            }
+        }
+        KeyCode::Char('t') => {
+            if let Some(id) = current {
+                app.prompt = Some((Prompt::Deadline(id), String::new()));
+            }
+        }
+        KeyCode::Char(c @ '0'..='3') => {
+            if let Some(todo) = current.and_then(|id| store.get_mut(id)) {
+                let before = todo.clone();
+                todo.priority = if c == '0' { None } else { c.to_digit(10).map(|p| p as u8) };
+                todo.record_changes(&before);
+                let id = todo.id;
+                app.message = Some(format!("Updated #{}", id));
+                save(app, store, Action::Edited, id);
+            }
+        }
+        _ => {}
+    }
+
+    true
+}
+
+fn handle_prompt_key(app: &mut App, store: &mut Store, key: KeyEvent) {
+    let Some((_, input)) = app.prompt.as_mut() else {
+        return;
+    };
+
+    match key.code {
+        KeyCode::Esc => app.prompt = None,
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Char(c) => input.push(c),
+        KeyCode::Enter => {
+            if let Some((prompt, input)) = app.prompt.take() {
+                submit_prompt(app, store, prompt, input.trim());
+            }
+        }
+        _ => {}
+    }
+}
+
+fn submit_prompt(app: &mut App, store: &mut Store, prompt: Prompt, input: &str) {
+    match prompt {
+        Prompt::Filter => {
+            app.tag = None;
+            app.project = None;
+            if let Some(tag) = input.strip_prefix('+') {
+                app.tag = Some(tag.to_string());
+            } else if let Some(project) = input.strip_prefix('@') {
+                app.project = Some(project.to_string());
+            } else if !input.is_empty() {
+                app.message = Some("Filter must start with + or @".to_string());
+            }
+            app.selected = 0;
+        }
+        Prompt::Text(id) => {
+            if input.is_empty() {
+                return;
+            }
+            if let Some(todo) = store.get_mut(id) {
+                let before = todo.clone();
+                todo.text = input.to_string();
+                todo.record_changes(&before);
+                app.message = Some(format!("Updated #{}", id));
+                save(app, store, Action::Edited, id);
+            }
+        }
+        Prompt::Deadline(id) => {
+            let deadline = if input.is_empty() {
+                None
+            } else {
+                match parse_deadline(input) {
+                    Some(dt) => Some(dt),
+                    None => {
+                        app.message = Some(format!("Could not parse time \"{}\"", input));
+                        return;
+                    }
+                }
+            };
+            if let Some(todo) = store.get_mut(id) {
+                let before = todo.clone();
+                todo.deadline = deadline;
+                todo.record_changes(&before);
+                app.message = match deadline {
+                    Some(d) if d.instant() < Utc::now() => Some(format!("Updated #{} (deadline is in the past)", id)),
+                    _ => Some(format!("Updated #{}", id)),
+                };
+                save(app, store, Action::Edited, id);
+            }
+        }
+    }
+}
+
+/// Save after a change to task `id`, and log it.
+fn save(app: &mut App, store: &Store, action: Action, id: u32) {
+    if let Err(e) = store.save_with_undo() {
+        app.message = Some(format!("Could not save: {}", e));
+        return;
+    }
+
+    let entries: Vec<Activity> = store.get(id).map(|t| Activity::new(action, t)).into_iter().collect();
+    if let Err(e) = activity::append(&entries) {
+        app.message = Some(format!("Could not write the activity log: {}", e));
+    }
+}
+
+fn draw(app: &mut App, store: &Store, config: &DisplayConfig) -> io::Result<()> {
+    let (cols, rows) = terminal::size()?;
+    let (cols, rows) = (cols as usize, rows as usize);
+
+    let ids = visible_ids(app, store);
+    app.selected = app.selected.min(ids.len().saturating_sub(1));
+
+    // Header, blank line, list, blank line, message/prompt, help
+    let list_height = rows.saturating_sub(5).max(1);
+    if app.selected < app.scroll {
+        app.scroll = app.selected;
+    } else if app.selected >= app.scroll + list_height {
+        app.scroll = app.selected + 1 - list_height;
+    }
+
+    let todos: Vec<_> = ids.iter().filter_map(|id| store.todos.iter().find(|t| t.id == *id)).collect();
+    let mut row_config = config.clone();
+    row_config.width = Some(cols.saturating_sub(2));
+    row_config.wrap = false;
+    let lines = render_rows(&todos, View::List, &row_config);
+
+    let mut out = io::stdout();
+    queue!(out, terminal::Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+
+    let mut header = format!("tsk — {} ({})", VIEWS[app.view].1, ids.len());
+    if let Some(ref tag) = app.tag {
+        header.push_str(&format!("  +{}", tag));
+    }
+    if let Some(ref project) = app.project {
+        header.push_str(&format!("  @{}", project));
+    }
+    queue!(out, SetAttribute(Attribute::Bold), Print(header), SetAttribute(Attribute::Reset))?;
+
+    if lines.is_empty() {
+        queue!(out, cursor::MoveTo(0, 2), Print("  No tasks."))?;
+    }
+
+    for (i, line) in lines.iter().enumerate().skip(app.scroll).take(list_height) {
+        let y = (2 + i - app.scroll) as u16;
+        let marker = if i == app.selected { "▶ " } else { "  " };
+        queue!(out, cursor::MoveTo(0, y), Print(marker), Print(line))?;
+    }
+
+    queue!(
+        out,
+        cursor::MoveTo(0, rows.saturating_sub(1) as u16),
+        SetAttribute(Attribute::Dim),
+        Print(HELP),
+        SetAttribute(Attribute::Reset)
+    )?;
+
+    // Drawn last so the cursor is left at the end of the prompt input
+    queue!(out, cursor::MoveTo(0, rows.saturating_sub(2) as u16))?;
+    if let Some((ref prompt, ref input)) = app.prompt {
+        queue!(out, Print(prompt.label()), Print(input), cursor::Show)?;
+    } else {
+        if let Some(ref message) = app.message {
+            queue!(out, Print(message))?;
+        }
+        queue!(out, cursor::Hide)?;
+    }
+
+    out.flush()
+}
@@ -1,13 +1,18 @@
-use crate::display::{print_undo_success, DisplayConfig};
+use crate::display::{print_error, print_undo_success, DisplayConfig};
 use crate::store::Store;
 
-pub fn run(config: &DisplayConfig) {
+pub fn run(store: &mut Store, config: &DisplayConfig) {
     match Store::undo() {
-        Ok(store) => {
+        Ok(restored) => {
+            *store = restored;
             print_undo_success(store.todos.len(), config);
         }
         Err(_) => {
-            println!("Nothing to undo.");
+            if config.is_machine() {
+                print_error("Nothing to undo");
+            } else {
+                println!("Nothing to undo.");
+            }
         }
     }
 }
@@ -0,0 +1,569 @@
+use crate::alias::{self, Aliases};
+use crate::cli::{Cli, SortBy};
+use crate::display::{Column, DisplayConfig, Layout, View};
+use crate::rules::{PatternRule, TagRules};
+use crate::time::{
+    parse_holidays, parse_time_of_day, parse_weekday, ClockStyle, DateOrder, Resolution, TimeOptions, WeekMode,
+    WeekStart, WorkCalendar,
+};
+use chrono::{NaiveDate, NaiveTime};
+use regex::RegexBuilder;
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use std::env;
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+
+/// Name of the per-directory config file, looked up from the current
+/// directory towards the root.
+pub const LOCAL_FILE: &str = ".tsk.toml";
+
+/// Settings `tsk config list` always shows, with their defaults as TOML.
+/// An empty default means the setting is unset.
+pub const SETTINGS: &[(&str, &str)] = &[
+    ("list.sort", "\"priority\""),
+    ("display.color", "\"auto\""),
+    ("display.text_width", "35"),
+    ("display.wrap", "false"),
+    ("bulk.confirm_threshold", "5"),
+    ("dates.resolution", "\"future\""),
+    ("dates.work_week", "[\"mon\", \"tue\", \"wed\", \"thu\", \"fri\"]"),
+    ("dates.holidays", ""),
+    ("dates.business_week", "false"),
+    ("dates.week_start", "\"monday\""),
+    ("dates.week", "\"rolling\""),
+    ("dates.clock", "\"12h\""),
+    ("dates.date_order", "\"mdy\""),
+    ("dates.all_day", "true"),
+    ("dates.default_time", "\"9:00\""),
+    ("dates.end_of_day", "\"23:59\""),
+    ("reminders.command", ""),
+];
+
+/// User settings from `~/.config/tsk/config.toml`, with the nearest
+/// `.tsk.toml` layered on top.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub list: ListConfig,
+    pub display: DisplaySettings,
+    pub bulk: BulkConfig,
+    pub views: ViewsConfig,
+    pub tags: TagsConfig,
+    pub dates: DatesConfig,
+    pub reminders: RemindersConfig,
+    pub aliases: HashMap<String, AliasConfig>,
+}
+
+/// The checked config, as commands use it once startup is done.
+#[derive(Debug, Default)]
+pub struct Settings {
+    pub aliases: Aliases,
+    pub layouts: HashMap<View, Layout>,
+    pub rules: TagRules,
+    pub display: DisplaySettings,
+    pub list: ListConfig,
+    pub bulk: BulkConfig,
+    /// Words of `reminders.command`
+    pub reminder_command: Option<Vec<String>>,
+}
+
+/// A user command: `standup = "ls -P work --by time"`, or a macro running
+/// several in turn: `morning = ["overdue", "today"]`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged, expecting = "expected a command or an array of commands")]
+pub enum AliasConfig {
+    Command(String),
+    Macro(Vec<String>),
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ListConfig {
+    /// Default for `--by`
+    pub sort: Option<SortBy>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BulkConfig {
+    /// Bulk changes to more tasks than this ask first; 5 if unset
+    pub confirm_threshold: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisplaySettings {
+    pub color: ColorMode,
+    /// Text column width when the terminal width is unknown (e.g. piped output)
+    pub text_width: Option<NonZeroUsize>,
+    /// Wrap long task text, as with `--wrap`
+    pub wrap: bool,
+}
+
+impl DisplaySettings {
+    /// Output settings for one command line: its flags, falling back to
+    /// these settings.
+    pub fn display_config(
+        &self,
+        cli: &Cli,
+        layouts: &HashMap<View, Layout>,
+        layout_override: Option<Layout>,
+    ) -> DisplayConfig {
+        DisplayConfig::new(cli.get_color_mode().or(self.color.force()), cli.get_output_format())
+            .with_wrap(cli.wrap || self.wrap)
+            .with_text_width(self.text_width.map(|w| w.get()))
+            .with_layouts(layouts.clone())
+            .with_layout_override(layout_override)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    /// Color when writing to a terminal
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    /// The `--color`/`--no-color` flag this setting stands for, if any.
+    pub fn force(self) -> Option<bool> {
+        match self {
+            ColorMode::Auto => None,
+            ColorMode::Always => Some(true),
+            ColorMode::Never => Some(false),
+        }
+    }
+}
+
+/// Row layouts per view. `all`, `week` and `overdue` fall back to `list`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ViewsConfig {
+    pub list: Option<ViewConfig>,
+    pub all: Option<ViewConfig>,
+    pub week: Option<ViewConfig>,
+    pub overdue: Option<ViewConfig>,
+    pub today: Option<ViewConfig>,
+    pub project: Option<ViewConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ViewConfig {
+    pub columns: Option<Vec<Column>>,
+    pub template: Option<String>,
+}
+
+/// How dates typed on the command line are read.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatesConfig {
+    /// Reading of dates that leave the year, week or day open
+    pub resolution: Resolution,
+    /// Working days, e.g. `["sun", "mon", "tue", "wed", "thu"]`; Monday to Friday if unset
+    pub work_week: Option<Vec<String>>,
+    /// Holiday file: an ICS calendar or one `YYYY-MM-DD` date per line
+    pub holidays: Option<String>,
+    /// Count "this week" in working days
+    pub business_week: bool,
+    pub week_start: WeekStart,
+    /// `rolling` (next 7 days) or `calendar` (rest of this week) for `tsk week`
+    pub week: WeekMode,
+    /// `12h` or `24h`
+    pub clock: ClockStyle,
+    /// `mdy`, `dmy` or `ymd`, for typing and showing numeric dates
+    pub date_order: DateOrder,
+    /// Make dates without a time all-day deadlines; true if unset
+    pub all_day: Option<bool>,
+    /// Time given to a named day (`friday`) when not all-day, e.g. `"9:00"`
+    pub default_time: Option<String>,
+    /// Time given to `today`, `eod` or `end of month` when not all-day
+    pub end_of_day: Option<String>,
+}
+
+/// How `tsk daemon` delivers reminders.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RemindersConfig {
+    /// Command to run per reminder, e.g. `notify-send tsk {text}`; printed if unset
+    pub command: Option<String>,
+}
+
+/// Tag aliases, implications and auto-tagging rules.
+///
+/// ```toml
+/// [tags.aliases]
+/// fe = "frontend"
+///
+/// [tags.implies]
+/// bug = ["work"]
+///
+/// [[tags.rules]]
+/// pattern = "review PR"
+/// tags = ["review"]
+/// project = "eng"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TagsConfig {
+    pub aliases: HashMap<String, String>,
+    pub implies: HashMap<String, Vec<String>>,
+    pub rules: Vec<RuleConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleConfig {
+    /// Regular expression matched case-insensitively against task text
+    pub pattern: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub project: Option<String>,
+}
+
+impl ViewConfig {
+    fn layout(&self, key: &str) -> io::Result<Layout> {
+        match (&self.columns, &self.template) {
+            (Some(_), Some(_)) => Err(invalid(format!(
+                "views.{}: set either columns or template, not both",
+                key
+            ))),
+            (Some(columns), None) => Ok(Layout::Columns(columns.clone())),
+            (None, Some(template)) => {
+                Layout::parse_template(template).map_err(|e| invalid(format!("views.{}.template: {}", key, e)))
+            }
+            (None, None) => Err(invalid(format!("views.{}: expected columns or template", key))),
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> io::Result<Self> {
+        let layers = Self::layers()?;
+        Self::from_layers(&layers)
+    }
+
+    /// Merge layers, later ones winning key by key.
+    pub fn from_layers(layers: &[Layer]) -> io::Result<Self> {
+        let mut merged = toml::Table::new();
+        for layer in layers {
+            merge(&mut merged, layer.resolved());
+        }
+
+        toml::Value::Table(merged).try_into().map_err(|e| invalid(e.to_string()))
+    }
+
+    /// The global file, then the nearest `.tsk.toml`, as far as they exist.
+    pub fn layers() -> io::Result<Vec<Layer>> {
+        let mut layers = Vec::new();
+        for path in [Some(Self::path()?), Self::local_path()].into_iter().flatten() {
+            if path.exists() {
+                let content = fs::read_to_string(&path)?;
+                layers.push(Layer::parse(path, &content)?);
+            }
+        }
+        Ok(layers)
+    }
+
+    pub fn path() -> io::Result<PathBuf> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Home directory not found"))?;
+
+        Ok(home.join(".config").join("tsk").join("config.toml"))
+    }
+
+    /// The nearest `.tsk.toml` in the current directory or a parent.
+    pub fn local_path() -> Option<PathBuf> {
+        let cwd = env::current_dir().ok()?;
+        cwd.ancestors().map(|dir| dir.join(LOCAL_FILE)).find(|path| path.is_file())
+    }
+
+    /// Check everything that is only read on use, so a bad value is reported
+    /// up front.
+    pub fn validate(&self) -> io::Result<()> {
+        self.layouts()?;
+        self.tag_rules()?;
+        self.time_options()?;
+        self.aliases()?;
+        self.reminder_command()?;
+        Ok(())
+    }
+
+    /// Resolve the configured layout for each view.
+    pub fn layouts(&self) -> io::Result<HashMap<View, Layout>> {
+        let views = &self.views;
+        let list = views.list.as_ref();
+        let entries = [
+            (View::List, "list", list),
+            (View::All, "all", views.all.as_ref().or(list)),
+            (View::Week, "week", views.week.as_ref().or(list)),
+            (View::Overdue, "overdue", views.overdue.as_ref().or(list)),
+            (View::Today, "today", views.today.as_ref()),
+            (View::Project, "project", views.project.as_ref()),
+        ];
+
+        let mut layouts = HashMap::new();
+        for (view, key, view_config) in entries {
+            if let Some(view_config) = view_config {
+                layouts.insert(view, view_config.layout(key)?);
+            }
+        }
+
+        Ok(layouts)
+    }
+
+    /// Resolve the `[dates]` section, reading the holiday file if set.
+    pub fn time_options(&self) -> io::Result<TimeOptions> {
+        let work_days = match &self.dates.work_week {
+            Some(days) => days
+                .iter()
+                .map(|d| {
+                    parse_weekday(&d.to_lowercase())
+                        .ok_or_else(|| invalid(format!("dates.work_week: unknown day \"{}\"", d)))
+                })
+                .collect::<io::Result<Vec<_>>>()?,
+            None => WorkCalendar::default().work_days().to_vec(),
+        };
+        let holidays = match &self.dates.holidays {
+            Some(path) => load_holidays(path)?,
+            None => BTreeSet::new(),
+        };
+        let defaults = TimeOptions::default();
+
+        Ok(TimeOptions {
+            resolution: self.dates.resolution,
+            display_zone: None,
+            calendar: WorkCalendar::new(work_days, holidays),
+            business_week: self.dates.business_week,
+            week_start: self.dates.week_start,
+            week: self.dates.week,
+            clock: self.dates.clock,
+            date_order: self.dates.date_order,
+            all_day: self.dates.all_day.unwrap_or(defaults.all_day),
+            default_time: time_setting("dates.default_time", &self.dates.default_time)?
+                .unwrap_or(defaults.default_time),
+            end_of_day: time_setting("dates.end_of_day", &self.dates.end_of_day)?.unwrap_or(defaults.end_of_day),
+        })
+    }
+
+    /// Split `reminders.command` into words.
+    pub fn reminder_command(&self) -> io::Result<Option<Vec<String>>> {
+        let Some(command) = &self.reminders.command else {
+            return Ok(None);
+        };
+        match shlex::split(command) {
+            Some(words) if !words.is_empty() => Ok(Some(words)),
+            Some(_) => Err(invalid("reminders.command: empty command".to_string())),
+            None => Err(invalid(format!("reminders.command: unbalanced quotes in \"{}\"", command))),
+        }
+    }
+
+    /// Split the `[aliases]` section into words, rejecting names taken by
+    /// built-in commands and aliases that expand to themselves.
+    pub fn aliases(&self) -> io::Result<Aliases> {
+        let mut commands = HashMap::new();
+        for (name, alias) in &self.aliases {
+            if alias::is_builtin(name) {
+                return Err(invalid(format!("aliases.{}: \"{}\" is a built-in command", name, name)));
+            }
+
+            let lines = match alias {
+                AliasConfig::Command(line) => std::slice::from_ref(line),
+                AliasConfig::Macro(lines) => lines.as_slice(),
+            };
+            let mut steps = Vec::new();
+            for line in lines {
+                match shlex::split(line) {
+                    Some(words) if !words.is_empty() => steps.push(words),
+                    Some(_) => return Err(invalid(format!("aliases.{}: empty command", name))),
+                    None => return Err(invalid(format!("aliases.{}: unbalanced quotes in \"{}\"", name, line))),
+                }
+            }
+            if steps.is_empty() {
+                return Err(invalid(format!("aliases.{}: empty command", name)));
+            }
+            commands.insert(name.clone(), steps);
+        }
+
+        let aliases = Aliases::new(commands);
+        for name in self.aliases.keys() {
+            aliases
+                .expand(std::slice::from_ref(name))
+                .map_err(|e| invalid(format!("aliases.{}: {}", name, e)))?;
+        }
+        Ok(aliases)
+    }
+
+    /// Compile the `[tags]` section.
+    pub fn tag_rules(&self) -> io::Result<TagRules> {
+        let mut patterns = Vec::new();
+        for (i, rule) in self.tags.rules.iter().enumerate() {
+            let pattern = RegexBuilder::new(&rule.pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|e| invalid(format!("tags.rules[{}].pattern: {}", i, e)))?;
+            patterns.push(PatternRule {
+                pattern,
+                tags: rule.tags.clone(),
+                project: rule.project.clone(),
+            });
+        }
+
+        Ok(TagRules::new(self.tags.aliases.clone(), self.tags.implies.clone(), patterns))
+    }
+}
+
+/// One config file, parsed.
+pub struct Layer {
+    pub path: PathBuf,
+    pub table: toml::Table,
+}
+
+impl Layer {
+    /// Parse and check a config file. Errors name the offending key where
+    /// possible, e.g. `display.text_width: invalid type`.
+    pub fn parse(path: PathBuf, content: &str) -> io::Result<Self> {
+        let describe = |e: toml::de::Error| match e.span().and_then(|span| key_at(content, span.start)) {
+            Some(key) => invalid(format!("{}: {}: {}", path.display(), key, e.message().trim_end())),
+            None => invalid(format!("{}: {}", path.display(), e)),
+        };
+        toml::from_str::<Config>(content).map_err(describe)?;
+        let table = toml::from_str(content).map_err(describe)?;
+        Ok(Layer { path, table })
+    }
+
+    /// The table with a relative holiday file made relative to this file.
+    fn resolved(&self) -> toml::Table {
+        let mut table = self.table.clone();
+        let dates = table.get_mut("dates").and_then(|d| d.as_table_mut());
+        if let Some(toml::Value::String(holidays)) = dates.and_then(|d| d.get_mut("holidays")) {
+            if !holidays.starts_with("~/") {
+                let dir = self.path.parent().unwrap_or(Path::new(""));
+                *holidays = dir.join(&*holidays).to_string_lossy().into_owned();
+            }
+        }
+        table
+    }
+}
+
+/// Set `key` (dotted, e.g. `display.color`) in the global config file, or
+/// in the nearest `.tsk.toml` if `local`. The file keeps its comments and
+/// layout, and is only written if the result is valid. Returns the path.
+pub fn set_value(key: &str, value: &str, local: bool) -> io::Result<PathBuf> {
+    let global = Config::path()?;
+    let target = if local {
+        match Config::local_path() {
+            Some(path) => path,
+            None => env::current_dir()?.join(LOCAL_FILE),
+        }
+    } else {
+        global.clone()
+    };
+
+    let content = if target.exists() { fs::read_to_string(&target)? } else { String::new() };
+    let mut doc: toml_edit::DocumentMut =
+        content.parse().map_err(|e| invalid(format!("{}: {}", target.display(), e)))?;
+
+    let parts: Vec<&str> = key.split('.').collect();
+    if parts.iter().any(|p| p.trim().is_empty()) {
+        return Err(invalid(format!("invalid setting name \"{}\"", key)));
+    }
+    // Anything that isn't a TOML value (`always`, `9:00`) is taken as a string
+    let value = value.parse::<toml_edit::Value>().unwrap_or_else(|_| value.into());
+
+    let mut item = doc.as_item_mut();
+    for (i, part) in parts[..parts.len() - 1].iter().enumerate() {
+        if item.get(part).is_none() {
+            item[part] = toml_edit::table();
+        }
+        item = &mut item[part];
+        if !item.is_table_like() {
+            return Err(invalid(format!("{}: not a section", parts[..=i].join("."))));
+        }
+    }
+    item[parts[parts.len() - 1]] = toml_edit::value(value);
+    let content = doc.to_string();
+
+    // Check the result together with the other file, as it will be loaded
+    let mut layers = Vec::new();
+    for path in [Some(global), Config::local_path().or(local.then(|| target.clone()))].into_iter().flatten() {
+        if path == target {
+            layers.push(Layer::parse(path, &content)?);
+        } else if path.exists() {
+            let existing = fs::read_to_string(&path)?;
+            layers.push(Layer::parse(path, &existing)?);
+        }
+    }
+    Config::from_layers(&layers)?.validate()?;
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&target, content)?;
+    Ok(target)
+}
+
+/// The dotted key on the `key = value` line holding `offset`, e.g.
+/// `display.wrap`, using the nearest `[section]` header above it.
+fn key_at(content: &str, offset: usize) -> Option<String> {
+    let before = content.get(..offset)?;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let (key, _) = content[line_start..].lines().next()?.split_once('=')?;
+    let key = key.trim().trim_matches('"');
+
+    let section = before[..line_start]
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| line.starts_with('['))
+        .map(|line| line.trim_matches(|c| c == '[' || c == ']').trim().to_string());
+    Some(match section {
+        Some(section) => format!("{}.{}", section, key),
+        None => key.to_string(),
+    })
+}
+
+/// Merge `layer` into `base`, descending into tables so a local file can
+/// override single keys of a section.
+fn merge(base: &mut toml::Table, layer: toml::Table) {
+    for (key, value) in layer {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(table)) => merge(base, table),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn time_setting(key: &str, value: &Option<String>) -> io::Result<Option<NaiveTime>> {
+    match value {
+        Some(s) => parse_time_of_day(s)
+            .map(Some)
+            .ok_or_else(|| invalid(format!("{}: expected a time like \"9:00\" or \"5pm\", got \"{}\"", key, s))),
+        None => Ok(None),
+    }
+}
+
+/// Read a holiday file; `~/` is the home directory. Relative paths have
+/// already been resolved against the config file that named them.
+fn load_holidays(path: &str) -> io::Result<BTreeSet<NaiveDate>> {
+    let full = match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => PathBuf::from(path),
+    };
+
+    let content =
+        fs::read_to_string(&full).map_err(|e| invalid(format!("dates.holidays: {}: {}", full.display(), e)))?;
+    parse_holidays(&content).map_err(|e| invalid(format!("dates.holidays: {}: {}", full.display(), e)))
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
@@ -0,0 +1,146 @@
+use crate::time::{from_viewer, localize, to_viewer};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Wall-clock format for floating deadlines, which carry no offset.
+const FLOATING_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+const DAY_FORMAT: &str = "%Y-%m-%d";
+
+/// When a task is due.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Deadline {
+    /// A fixed moment, stored in UTC. `zone` is the zone it was entered in,
+    /// if one was named; it is kept for reference and never used to shift it.
+    At { time: DateTime<Utc>, zone: Option<Tz> },
+    /// A wall-clock time with no zone: due at that local time wherever the
+    /// viewer is, so it never shifts when travelling.
+    Floating(NaiveDateTime),
+    /// A whole day with no time, due by the end of that day wherever the
+    /// viewer is.
+    Day(NaiveDate),
+}
+
+impl Deadline {
+    pub fn at(time: DateTime<Utc>) -> Self {
+        Deadline::At { time, zone: None }
+    }
+
+    /// The deadline in the viewer's zone (`--tz`, or the system zone).
+    /// All-day deadlines give the start of their day.
+    pub fn in_viewer_zone(self) -> DateTime<FixedOffset> {
+        match self {
+            Deadline::At { time, .. } => to_viewer(time),
+            Deadline::Floating(naive) => from_viewer(naive).unwrap_or_else(|| to_viewer(naive.and_utc())),
+            Deadline::Day(date) => Deadline::Floating(date.and_time(NaiveTime::MIN)).in_viewer_zone(),
+        }
+    }
+
+    /// The moment the deadline passes, as seen by the viewer. All-day
+    /// deadlines pass when their day ends.
+    pub fn instant(self) -> DateTime<Utc> {
+        match self {
+            Deadline::At { time, .. } => time,
+            Deadline::Floating(_) => self.in_viewer_zone().with_timezone(&Utc),
+            Deadline::Day(date) => Deadline::Floating(date.and_time(NaiveTime::MIN) + Duration::days(1)).instant(),
+        }
+    }
+
+    pub fn is_all_day(self) -> bool {
+        matches!(self, Deadline::Day(_))
+    }
+
+    /// Order by day, with all-day deadlines before timed ones on the same day.
+    pub fn sort_key(self) -> (NaiveDate, Option<DateTime<Utc>>) {
+        match self {
+            Deadline::Day(date) => (date, None),
+            _ => (self.in_viewer_zone().date_naive(), Some(self.instant())),
+        }
+    }
+
+    /// The same time of day on another date, read in the zone the deadline
+    /// was entered in.
+    pub fn on_date(self, date: NaiveDate) -> Option<Deadline> {
+        match self {
+            Deadline::At { time, zone: Some(tz) } => {
+                let wall = time.with_timezone(&tz).time();
+                let time = localize(&tz, date.and_time(wall))?.with_timezone(&Utc);
+                Some(Deadline::At { time, zone: Some(tz) })
+            }
+            Deadline::At { time, zone: None } => {
+                let wall = to_viewer(time).time();
+                Some(Deadline::at(from_viewer(date.and_time(wall))?.with_timezone(&Utc)))
+            }
+            Deadline::Floating(naive) => Some(Deadline::Floating(date.and_time(naive.time()))),
+            Deadline::Day(_) => Some(Deadline::Day(date)),
+        }
+    }
+
+    pub fn zone(self) -> Option<Tz> {
+        match self {
+            Deadline::At { zone, .. } => zone,
+            Deadline::Floating(_) | Deadline::Day(_) => None,
+        }
+    }
+}
+
+/// Stored as a string: `2026-03-13T20:00:00Z`, with the entry zone appended
+/// as `[America/New_York]` if one was named, or `2026-03-13T09:00:00` with
+/// no offset for floating deadlines, or `2026-03-13` for all-day ones.
+/// Plain RFC 3339 strings with any offset, as written by older versions,
+/// read back as fixed moments.
+impl fmt::Display for Deadline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Deadline::At { time, zone } => {
+                write!(f, "{}", time.to_rfc3339_opts(SecondsFormat::AutoSi, true))?;
+                if let Some(zone) = zone {
+                    write!(f, "[{}]", zone.name())?;
+                }
+                Ok(())
+            }
+            Deadline::Floating(naive) => write!(f, "{}", naive.format(FLOATING_FORMAT)),
+            Deadline::Day(date) => write!(f, "{}", date.format(DAY_FORMAT)),
+        }
+    }
+}
+
+impl std::str::FromStr for Deadline {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (stamp, zone) = match s.strip_suffix(']').and_then(|rest| rest.split_once('[')) {
+            Some((stamp, zone)) => (stamp, Some(zone.parse::<Tz>().map_err(|e| e.to_string())?)),
+            None => (s, None),
+        };
+
+        if let Ok(time) = DateTime::parse_from_rfc3339(stamp) {
+            return Ok(Deadline::At {
+                time: time.with_timezone(&Utc),
+                zone,
+            });
+        }
+
+        if let Ok(date) = NaiveDate::parse_from_str(stamp, DAY_FORMAT) {
+            return Ok(Deadline::Day(date));
+        }
+
+        NaiveDateTime::parse_from_str(stamp, FLOATING_FORMAT)
+            .map(Deadline::Floating)
+            .map_err(|_| format!("invalid deadline \"{}\"", s))
+    }
+}
+
+impl Serialize for Deadline {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Deadline {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -1,118 +1,567 @@
-use crate::time::{format_completed_time, format_deadline};
+use crate::deadline::Deadline;
+use crate::select::CONFIRM_THRESHOLD;
+use crate::time::{
+    clock_format, format_age, format_completed_time, format_deadline, format_duration, is_due_today, long_date_format,
+};
 use crate::todo::Todo;
+use chrono::Utc;
 use colored::Colorize;
-use std::io::{self, IsTerminal};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use serde_json::json;
+use std::io::{self, IsTerminal, Write};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set once errors should be reported as JSON on stderr.
+static JSON_ERRORS: AtomicBool = AtomicBool::new(false);
+
+/// Set by `print_error` so `main` can exit nonzero.
+static FAILED: AtomicBool = AtomicBool::new(false);
+
+/// Result of a mutating command in `--json`/`--jsonl` output.
+#[derive(Serialize)]
+struct MutationResult<'a> {
+    status: &'static str,
+    action: &'a str,
+    count: usize,
+    tasks: &'a [Todo],
+}
+
+/// Error written to stderr in `--json`/`--jsonl` output.
+#[derive(Serialize)]
+struct ErrorResult<'a> {
+    status: &'static str,
+    error: &'a str,
+}
+
+/// Warning written to stderr in `--json`/`--jsonl` output.
+#[derive(Serialize)]
+struct WarningResult<'a> {
+    status: &'static str,
+    warning: &'a str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Jsonl,
+}
 
+#[derive(Clone)]
 pub struct DisplayConfig {
     pub use_color: bool,
+    pub format: OutputFormat,
+    /// Terminal width in columns, if known
+    pub width: Option<usize>,
+    /// Wrap long text instead of eliding it
+    pub wrap: bool,
+    /// Text column width when the terminal width is unknown
+    pub text_width: usize,
+    /// Bulk changes to more tasks than this ask first
+    pub confirm_threshold: usize,
+    layouts: HashMap<View, Layout>,
+    layout_override: Option<Layout>,
 }
 
 impl DisplayConfig {
-    pub fn new(force_color: Option<bool>) -> Self {
+    pub fn new(force_color: Option<bool>, format: OutputFormat) -> Self {
         let use_color = match force_color {
             Some(c) => c,
             None => io::stdout().is_terminal(),
         };
-        Self { use_color }
+        let machine = format != OutputFormat::Text;
+        JSON_ERRORS.store(machine, Ordering::Relaxed);
+        Self {
+            use_color: use_color && !machine,
+            format,
+            width: terminal_width(),
+            wrap: false,
+            text_width: DEFAULT_TEXT_WIDTH,
+            confirm_threshold: CONFIRM_THRESHOLD,
+            layouts: HashMap::new(),
+            layout_override: None,
+        }
+    }
+
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn with_text_width(mut self, width: Option<usize>) -> Self {
+        self.text_width = width.unwrap_or(DEFAULT_TEXT_WIDTH);
+        self
+    }
+
+    pub fn with_confirm_threshold(mut self, threshold: Option<usize>) -> Self {
+        self.confirm_threshold = threshold.unwrap_or(CONFIRM_THRESHOLD);
+        self
+    }
+
+    /// Per-view layouts from the config file.
+    pub fn with_layouts(mut self, layouts: HashMap<View, Layout>) -> Self {
+        self.layouts = layouts;
+        self
+    }
+
+    /// Layout given on the command line, applied to every view.
+    pub fn with_layout_override(mut self, layout: Option<Layout>) -> Self {
+        self.layout_override = layout;
+        self
+    }
+
+    pub fn layout(&self, view: View) -> Layout {
+        self.layout_override
+            .as_ref()
+            .or_else(|| self.layouts.get(&view))
+            .cloned()
+            .unwrap_or_else(|| view.default_layout())
+    }
+
+    /// True when output is JSON or JSON Lines rather than human text.
+    pub fn is_machine(&self) -> bool {
+        self.format != OutputFormat::Text
     }
 }
 
-pub fn format_todo(todo: &Todo, config: &DisplayConfig) -> String {
-    let id = format!("{:>3}", todo.id);
-    let priority = todo.priority_display();
-    let text = truncate(&todo.text, 35);
+/// Width of the terminal on stdout, or `$COLUMNS` if set.
+fn terminal_width() -> Option<usize> {
+    if let Some(cols) = std::env::var("COLUMNS").ok().and_then(|c| c.parse().ok()) {
+        return Some(cols);
+    }
 
-    let deadline_str = if todo.done {
-        if let Some(completed) = todo.completed_at {
-            format_completed_time(completed)
-        } else {
-            "done".to_string()
+    terminal_size::terminal_size().map(|(terminal_size::Width(w), _)| w as usize)
+}
+
+/// A column in a task row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Column {
+    Id,
+    Uuid,
+    Priority,
+    Text,
+    Due,
+    Created,
+    Age,
+    Project,
+    Tags,
+    Status,
+    Estimate,
+}
+
+impl Column {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "id" => Some(Column::Id),
+            "uuid" => Some(Column::Uuid),
+            "priority" => Some(Column::Priority),
+            "text" => Some(Column::Text),
+            "due" => Some(Column::Due),
+            "created" => Some(Column::Created),
+            "age" => Some(Column::Age),
+            "project" => Some(Column::Project),
+            "tags" => Some(Column::Tags),
+            "status" => Some(Column::Status),
+            "estimate" => Some(Column::Estimate),
+            _ => None,
         }
-    } else if let Some(deadline) = todo.deadline {
-        format_deadline(deadline, todo.is_overdue())
-    } else {
-        "—".to_string()
-    };
+    }
+
+    /// Fixed column width, or `None` to size to the content.
+    fn width(self) -> Option<usize> {
+        match self {
+            Column::Status => Some(1),
+            Column::Id | Column::Priority => Some(3),
+            Column::Uuid => Some(8),
+            Column::Due => Some(18),
+            Column::Created => Some(10),
+            Column::Age | Column::Estimate => Some(5),
+            Column::Text | Column::Project | Column::Tags => None,
+        }
+    }
 
-    let project_str = todo
-        .project
-        .as_ref()
-        .map(|p| format!("@{}", p))
-        .unwrap_or_default();
+    fn is_right_aligned(self) -> bool {
+        self == Column::Id
+    }
 
-    let tags_str = if todo.tags.is_empty() {
-        String::new()
-    } else {
-        todo.tags.iter().map(|t| format!("+{}", t)).collect::<Vec<_>>().join(" ")
+    fn is_metadata(self) -> bool {
+        matches!(self, Column::Project | Column::Tags)
+    }
+
+    /// Status, id and priority sit one space apart, as do project and tags.
+    fn packs_with(self, next: Column) -> bool {
+        let leading = |c| matches!(c, Column::Status | Column::Id | Column::Priority);
+        (leading(self) && leading(next)) || (self.is_metadata() && next.is_metadata())
+    }
+}
+
+/// How a date field is rendered in a template (`{due:date}`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateStyle {
+    Relative,
+    Date,
+    Iso,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    Literal(String),
+    Field(Column, Option<DateStyle>),
+}
+
+/// Row layout: either a list of columns or a template string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Layout {
+    Columns(Vec<Column>),
+    Template(Vec<Segment>),
+}
+
+impl Layout {
+    /// Parse a template such as `"{id} {text} ({due:relative})"`. Use `{{` and
+    /// `}}` for literal braces.
+    pub fn parse_template(template: &str) -> Result<Self, String> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut field = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => field.push(c),
+                            None => return Err(format!("unclosed \"{{{}\"", field)),
+                        }
+                    }
+
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(parse_field(field.trim())?);
+                }
+                '}' => return Err("unmatched \"}\"".to_string()),
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Layout::Template(segments))
+    }
+}
+
+fn parse_field(field: &str) -> Result<Segment, String> {
+    let (name, style) = match field.split_once(':') {
+        Some((name, style)) => (name, Some(style)),
+        None => (field, None),
     };
 
-    // Combine project and tags
-    let metadata_str = if project_str.is_empty() && tags_str.is_empty() {
-        String::new()
-    } else if project_str.is_empty() {
-        tags_str.clone()
-    } else if tags_str.is_empty() {
-        project_str.clone()
-    } else {
-        format!("{} {}", project_str, tags_str)
+    let column = Column::from_name(name).ok_or_else(|| format!("unknown column \"{}\"", name))?;
+
+    let style = match style {
+        None => None,
+        Some(s) if !matches!(column, Column::Due | Column::Created) => {
+            return Err(format!("\"{}\" does not take a format (got \"{}\")", name, s));
+        }
+        Some("relative") => Some(DateStyle::Relative),
+        Some("date") => Some(DateStyle::Date),
+        Some("iso") => Some(DateStyle::Iso),
+        Some(s) => return Err(format!("unknown date format \"{}\" (use relative, date or iso)", s)),
     };
 
-    let checkmark = if todo.done { "✓" } else { " " };
+    Ok(Segment::Field(column, style))
+}
 
-    if config.use_color {
-        let id_colored = id.dimmed();
+/// Views whose row layout can be configured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum View {
+    List,
+    All,
+    Week,
+    Overdue,
+    Today,
+    Project,
+}
 
-        let priority_colored = match todo.priority {
-            Some(1) => "!!!".red().bold(),
-            Some(2) => "!! ".yellow(),
-            Some(3) => "!  ".blue(),
-            _ => "   ".normal(),
-        };
+impl View {
+    fn default_layout(self) -> Layout {
+        use Column::*;
+        match self {
+            View::List | View::All | View::Week | View::Overdue => {
+                Layout::Columns(vec![Status, Id, Priority, Text, Due, Project, Tags])
+            }
+            View::Today => Layout::Columns(vec![Id, Priority, Text, Due, Project, Tags]),
+            View::Project => Layout::Columns(vec![Id, Priority, Text, Due, Tags]),
+        }
+    }
 
-        let text_colored = if todo.done {
-            text.dimmed().strikethrough()
-        } else {
-            text.normal()
-        };
+    /// Sectioned views are indented under their headers.
+    fn indent(self) -> usize {
+        match self {
+            View::Today | View::Project => 3,
+            _ => 0,
+        }
+    }
+}
+
+/// Text column width when the terminal width is unknown (e.g. piped output),
+/// unless `display.text_width` is set.
+const DEFAULT_TEXT_WIDTH: usize = 35;
+
+/// Text is never squeezed narrower than this to fit the terminal.
+const MIN_TEXT_WIDTH: usize = 20;
+
+/// Render the lines for each task using the layout configured for `view`.
+pub fn render_rows(todos: &[&Todo], view: View, config: &DisplayConfig) -> Vec<String> {
+    let indent = view.indent();
+
+    match config.layout(view) {
+        Layout::Template(segments) => todos
+            .iter()
+            .map(|todo| render_template(todo, &segments, indent, config))
+            .collect(),
+        Layout::Columns(columns) => {
+            let cells: Vec<Vec<String>> = todos
+                .iter()
+                .map(|todo| columns.iter().map(|c| cell_text(todo, *c, None)).collect())
+                .collect();
+
+            let widths = column_widths(&columns, &cells, indent, config.width, config.text_width);
+
+            todos
+                .iter()
+                .zip(cells)
+                .flat_map(|(todo, row)| render_columns(todo, &columns, row, &widths, indent, config))
+                .collect()
+        }
+    }
+}
+
+fn render_template(todo: &Todo, segments: &[Segment], indent: usize, config: &DisplayConfig) -> String {
+    let cells: Vec<String> = segments
+        .iter()
+        .map(|seg| match seg {
+            Segment::Literal(s) => s.clone(),
+            Segment::Field(column, style) => cell_text(todo, *column, *style).trim_end().to_string(),
+        })
+        .collect();
+
+    // Squeeze text fields so the whole line fits the terminal
+    let is_text = |seg: &Segment| matches!(seg, Segment::Field(Column::Text, _));
+    let text_budget = config.width.map(|width| {
+        let fixed: usize = segments
+            .iter()
+            .zip(&cells)
+            .filter(|(seg, _)| !is_text(seg))
+            .map(|(_, cell)| display_width(cell))
+            .sum();
+        width.saturating_sub(indent + fixed).max(MIN_TEXT_WIDTH)
+    });
+
+    let row: String = segments
+        .iter()
+        .zip(cells)
+        .map(|(seg, cell)| match seg {
+            Segment::Literal(_) => cell,
+            Segment::Field(column, _) => {
+                let cell = match text_budget {
+                    Some(budget) if *column == Column::Text => truncate(&cell, budget),
+                    _ => cell,
+                };
+                paint(todo, *column, &cell, config)
+            }
+        })
+        .collect();
+
+    format!("{}{}", " ".repeat(indent), row)
+}
 
-        let deadline_colored = if todo.done {
-            deadline_str.green()
-        } else if todo.is_overdue() {
-            deadline_str.red().bold()
-        } else if todo.deadline.map(|d| crate::time::is_due_today(d)).unwrap_or(false) {
-            deadline_str.yellow()
+/// Lay out one task as a row, plus continuation lines when wrapping text.
+fn render_columns(
+    todo: &Todo,
+    columns: &[Column],
+    row: Vec<String>,
+    widths: &[usize],
+    indent: usize,
+    config: &DisplayConfig,
+) -> Vec<String> {
+    let mut line = " ".repeat(indent);
+    let mut offset = indent;
+    let mut continuation = Vec::new();
+    let mut text_offset = 0;
+    let mut pending_sep = "";
+
+    for (i, (column, cell)) in columns.iter().zip(row).enumerate() {
+        line.push_str(pending_sep);
+        offset += pending_sep.len();
+
+        let cell = if *column == Column::Text && config.wrap {
+            let mut lines = wrap(&cell, widths[i]).into_iter();
+            let first = lines.next().unwrap_or_default();
+            continuation = lines.collect();
+            text_offset = offset;
+            first
         } else {
-            deadline_str.normal()
+            truncate(&cell, widths[i])
         };
 
-        let project_colored = project_str.magenta();
-        let tags_colored = tags_str.cyan();
-        let checkmark_colored = if todo.done { checkmark.green() } else { checkmark.normal() };
-
-        // Combine project and tags with colors
-        let metadata_colored = if project_str.is_empty() && tags_str.is_empty() {
-            String::new()
-        } else if project_str.is_empty() {
-            format!("{}", tags_colored)
-        } else if tags_str.is_empty() {
-            format!("{}", project_colored)
+        // Project and tags next to each other read as one "@proj +tag" field
+        let next = columns.get(i + 1).copied();
+        let joined = next.is_some_and(|n| column.is_metadata() && n.is_metadata());
+
+        let width = if joined { display_width(&cell) } else { widths[i] };
+        let pad = " ".repeat(width.saturating_sub(display_width(&cell)));
+        let painted = paint(todo, *column, &cell, config);
+        if column.is_right_aligned() {
+            line.push_str(&pad);
+            line.push_str(&painted);
         } else {
-            format!("{} {}", project_colored, tags_colored)
+            line.push_str(&painted);
+            line.push_str(&pad);
+        }
+        offset += width;
+
+        pending_sep = match next {
+            Some(_) if joined && cell.is_empty() => "",
+            Some(n) if column.packs_with(n) => " ",
+            Some(_) => "  ",
+            None => "",
         };
+    }
 
-        format!(
-            "{} {} {}  {:<35}  {:<18}  {}",
-            checkmark_colored, id_colored, priority_colored, text_colored, deadline_colored, metadata_colored
-        )
-    } else {
-        format!(
-            "{} {} {}  {:<35}  {:<18}  {}",
-            checkmark, id, priority, text, deadline_str, metadata_str
-        )
+    let mut lines = vec![line.trim_end().to_string()];
+    for part in continuation {
+        lines.push(format!("{}{}", " ".repeat(text_offset), paint(todo, Column::Text, &part, config)));
     }
+    lines
+}
+
+/// Size each column. With a known terminal width, text takes the space it
+/// needs; on overflow it shrinks first, then project and tags from the right.
+fn column_widths(
+    columns: &[Column],
+    cells: &[Vec<String>],
+    indent: usize,
+    term_width: Option<usize>,
+    text_width: usize,
+) -> Vec<usize> {
+    let natural = |i: usize| cells.iter().map(|row| display_width(&row[i])).max().unwrap_or(0);
+
+    let mut widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| c.width().unwrap_or_else(|| natural(i)))
+        .collect();
+
+    let text = columns.iter().position(|c| *c == Column::Text);
+
+    let Some(term_width) = term_width else {
+        if let Some(i) = text {
+            widths[i] = text_width;
+        }
+        return widths;
+    };
+
+    let seps: usize = columns
+        .windows(2)
+        .map(|pair| if pair[0].packs_with(pair[1]) { 1 } else { 2 })
+        .sum();
+    let total = indent + seps + widths.iter().sum::<usize>();
+    let mut overflow = total.saturating_sub(term_width);
+
+    let metadata = (0..columns.len())
+        .rev()
+        .filter(|&i| columns[i].is_metadata());
+
+    for i in text.into_iter().chain(metadata) {
+        if overflow == 0 {
+            break;
+        }
+        let min = if columns[i] == Column::Text { MIN_TEXT_WIDTH.min(widths[i]) } else { 0 };
+        let cut = overflow.min(widths[i] - min);
+        widths[i] -= cut;
+        overflow -= cut;
+    }
+
+    widths
+}
+
+fn cell_text(todo: &Todo, column: Column, style: Option<DateStyle>) -> String {
+    match column {
+        Column::Id => todo.id.to_string(),
+        Column::Uuid => todo.short_uuid().to_string(),
+        Column::Priority => todo.priority_display().to_string(),
+        Column::Text => todo.text.clone(),
+        Column::Status => if todo.done { "✓" } else { " " }.to_string(),
+        Column::Project => todo.project.as_ref().map(|p| format!("@{}", p)).unwrap_or_default(),
+        Column::Tags => todo.tags.iter().map(|t| format!("+{}", t)).collect::<Vec<_>>().join(" "),
+        Column::Estimate => todo.estimate.map(format_duration).unwrap_or_default(),
+        Column::Age => format_age(todo.created_at),
+        Column::Created => match style.unwrap_or(DateStyle::Date) {
+            DateStyle::Relative => format!("{} ago", format_age(todo.created_at)),
+            DateStyle::Date => todo.created_at.format("%Y-%m-%d").to_string(),
+            DateStyle::Iso => todo.created_at.to_rfc3339(),
+        },
+        Column::Due => match (style.unwrap_or(DateStyle::Relative), todo.deadline) {
+            (DateStyle::Relative, _) if todo.done => match todo.completed_at {
+                Some(completed) => format_completed_time(completed),
+                None => "done".to_string(),
+            },
+            (DateStyle::Relative, Some(deadline)) => format_deadline(deadline, todo.is_overdue()),
+            (DateStyle::Date, Some(deadline)) => deadline.in_viewer_zone().format("%Y-%m-%d").to_string(),
+            (DateStyle::Iso, Some(Deadline::Day(date))) => date.format("%Y-%m-%d").to_string(),
+            (DateStyle::Iso, Some(deadline)) => deadline.in_viewer_zone().to_rfc3339(),
+            (_, None) => "—".to_string(),
+        },
+    }
+}
+
+fn paint(todo: &Todo, column: Column, cell: &str, config: &DisplayConfig) -> String {
+    if !config.use_color {
+        return cell.to_string();
+    }
+
+    let colored = match column {
+        Column::Id | Column::Uuid | Column::Created | Column::Age | Column::Estimate => cell.dimmed(),
+        Column::Status if todo.done => cell.green(),
+        Column::Priority => match todo.priority {
+            Some(1) => cell.red().bold(),
+            Some(2) => cell.yellow(),
+            Some(3) => cell.blue(),
+            _ => cell.normal(),
+        },
+        Column::Text if todo.done => cell.dimmed().strikethrough(),
+        Column::Due if todo.done => cell.green(),
+        Column::Due if todo.is_overdue() => cell.red().bold(),
+        Column::Due if todo.deadline.map(is_due_today).unwrap_or(false) => cell.yellow(),
+        Column::Project => cell.magenta(),
+        Column::Tags => cell.cyan(),
+        _ => cell.normal(),
+    };
+
+    colored.to_string()
 }
 
 pub fn print_todo_added(todo: &Todo, config: &DisplayConfig) {
+    if config.is_machine() {
+        return;
+    }
+
     let mut parts = vec![format!("Added #{}: {}", todo.id, todo.text)];
 
     if let Some(p) = todo.priority {
@@ -126,7 +575,7 @@ pub fn print_todo_added(todo: &Todo, config: &DisplayConfig) {
     }
 
     if let Some(deadline) = todo.deadline {
-        parts.push(format_deadline(deadline, false));
+        parts.push(describe_deadline(deadline));
     }
 
     if let Some(ref project) = todo.project {
@@ -147,6 +596,10 @@ pub fn print_todo_added(todo: &Todo, config: &DisplayConfig) {
 }
 
 pub fn print_todo_completed(todo: &Todo, config: &DisplayConfig) {
+    if config.is_machine() {
+        return;
+    }
+
     let msg = format!("Completed #{}: {}", todo.id, todo.text);
     if config.use_color {
         println!("{}", msg.green());
@@ -155,7 +608,39 @@ pub fn print_todo_completed(todo: &Todo, config: &DisplayConfig) {
     }
 }
 
+pub fn print_todo_reopened(todo: &Todo, config: &DisplayConfig) {
+    if config.is_machine() {
+        return;
+    }
+
+    let msg = format!("Reopened #{}: {}", todo.id, todo.text);
+    if config.use_color {
+        println!("{}", msg.cyan());
+    } else {
+        println!("{}", msg);
+    }
+}
+
+/// "Deferred #3: call bob → mon 3:00pm".
+pub fn print_todo_rescheduled(verb: &str, todo: &Todo, config: &DisplayConfig) {
+    if config.is_machine() {
+        return;
+    }
+
+    let due = todo.deadline.map(describe_deadline).unwrap_or_default();
+    let msg = format!("{} #{}: {} → {}", verb, todo.id, todo.text, due);
+    if config.use_color {
+        println!("{}", msg.cyan());
+    } else {
+        println!("{}", msg);
+    }
+}
+
 pub fn print_todo_deleted(todo: &Todo, config: &DisplayConfig) {
+    if config.is_machine() {
+        return;
+    }
+
     let msg = format!("Deleted #{}: {}", todo.id, todo.text);
     if config.use_color {
         println!("{}", msg.yellow());
@@ -165,6 +650,10 @@ pub fn print_todo_deleted(todo: &Todo, config: &DisplayConfig) {
 }
 
 pub fn print_todo_updated(todo: &Todo, config: &DisplayConfig) {
+    if config.is_machine() {
+        return;
+    }
+
     let mut parts = vec![format!("Updated #{}: {}", todo.id, todo.text)];
 
     if let Some(p) = todo.priority {
@@ -178,7 +667,7 @@ pub fn print_todo_updated(todo: &Todo, config: &DisplayConfig) {
     }
 
     if let Some(deadline) = todo.deadline {
-        parts.push(format_deadline(deadline, false));
+        parts.push(describe_deadline(deadline));
     }
 
     if let Some(ref project) = todo.project {
@@ -199,6 +688,14 @@ pub fn print_todo_updated(todo: &Todo, config: &DisplayConfig) {
 }
 
 pub fn print_undo_success(task_count: usize, config: &DisplayConfig) {
+    if config.is_machine() {
+        print_json(
+            &json!({ "status": "ok", "action": "undo", "restored": task_count }),
+            config,
+        );
+        return;
+    }
+
     let msg = format!(
         "Undo successful. Restored {} task{}.",
         task_count,
@@ -212,18 +709,191 @@ pub fn print_undo_success(task_count: usize, config: &DisplayConfig) {
 }
 
 pub fn print_error(msg: &str) {
-    eprintln!("{}: {}", "Error".red().bold(), msg);
+    FAILED.store(true, Ordering::Relaxed);
+
+    if JSON_ERRORS.load(Ordering::Relaxed) {
+        let err = ErrorResult { status: "error", error: msg };
+        eprintln!("{}", serde_json::to_string(&err).unwrap_or_default());
+    } else {
+        eprintln!("{}: {}", "Error".red().bold(), msg);
+    }
+}
+
+/// Report something suspicious without failing the command.
+pub fn print_warning(msg: &str) {
+    if JSON_ERRORS.load(Ordering::Relaxed) {
+        let warning = WarningResult {
+            status: "warning",
+            warning: msg,
+        };
+        eprintln!("{}", serde_json::to_string(&warning).unwrap_or_default());
+    } else {
+        eprintln!("{}: {}", "Warning".yellow().bold(), msg);
+    }
+}
+
+/// The deadline plus the zone it was entered in, or "floating". A zone
+/// other than the viewer's shows its own wall time: `fri 7:00pm (fri 3:00pm
+/// America/New_York)`.
+fn describe_deadline(deadline: Deadline) -> String {
+    let when = format_deadline(deadline, false);
+    match deadline {
+        Deadline::At { time, zone: Some(zone) } => {
+            let entered = time.with_timezone(&zone);
+            if entered.naive_local() == deadline.in_viewer_zone().naive_local() {
+                format!("{} ({})", when, zone.name())
+            } else {
+                let format = format!("%a {}", clock_format());
+                format!("{} ({} {})", when, entered.format(&format).to_string().to_lowercase(), zone.name())
+            }
+        }
+        Deadline::Floating(_) => format!("{} (floating)", when),
+        _ => when,
+    }
+}
+
+/// Warn when a deadline being set has already passed.
+pub fn warn_if_past(deadline: Option<Deadline>) {
+    if let Some(deadline) = deadline.filter(|d| d.instant() < Utc::now()) {
+        let format = if deadline.is_all_day() {
+            long_date_format().to_string()
+        } else {
+            format!("{} at {}", long_date_format(), clock_format())
+        };
+        let shown = deadline.in_viewer_zone().format(&format);
+        print_warning(&format!("Deadline {} is in the past", shown));
+    }
+}
+
+/// Ask a yes/no question on the terminal. Returns `None` when stdin is not
+/// interactive.
+pub fn confirm(prompt: &str) -> Option<bool> {
+    if !io::stdin().is_terminal() {
+        return None;
+    }
+
+    print!("{} [y/N] ", prompt);
+    io::stdout().flush().ok()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).ok()?;
+    Some(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Process exit code: 1 if any error was reported, 0 otherwise.
+pub fn exit_code() -> i32 {
+    if FAILED.load(Ordering::Relaxed) {
+        1
+    } else {
+        0
+    }
 }
 
 pub fn print_empty_message() {
     println!("No open tasks. Use 'tsk add' to create one.");
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.chars().count() <= max_len {
-        s.to_string()
+/// Print a value as pretty JSON, or as a single compact line for `--jsonl`.
+pub fn print_json<T: Serialize + ?Sized>(value: &T, config: &DisplayConfig) {
+    let out = match config.format {
+        OutputFormat::Jsonl => serde_json::to_string(value),
+        _ => serde_json::to_string_pretty(value),
+    };
+
+    match out {
+        Ok(s) => println!("{}", s),
+        Err(e) => print_error(&format!("Could not serialize output: {}", e)),
+    }
+}
+
+/// Print a listing: a JSON array, or one item per line for `--jsonl`.
+pub fn print_json_list<T: Serialize>(items: &[T], config: &DisplayConfig) {
+    if config.format == OutputFormat::Jsonl {
+        for item in items {
+            print_json(item, config);
+        }
     } else {
-        let truncated: String = s.chars().take(max_len - 1).collect();
-        format!("{}…", truncated)
+        print_json(items, config);
+    }
+}
+
+/// Print the result of a mutation in machine-readable form. No-op for text
+/// output, where each task is reported as it is changed.
+pub fn print_mutation(action: &str, todos: &[Todo], config: &DisplayConfig) {
+    if !config.is_machine() {
+        return;
+    }
+
+    let result = MutationResult {
+        status: "ok",
+        action,
+        count: todos.len(),
+        tasks: todos,
+    };
+    print_json(&result, config);
+}
+
+fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// Shorten `s` to at most `max_width` terminal columns, ending in `…`. Breaks
+/// at a word boundary when one is close to the limit.
+pub fn truncate(s: &str, max_width: usize) -> String {
+    if display_width(s) <= max_width {
+        return s.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+
+    let mut out = String::new();
+    let mut width = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if width + w > max_width - 1 {
+            break;
+        }
+        out.push(c);
+        width += w;
+    }
+
+    if let Some(pos) = out.rfind(' ') {
+        if display_width(&out[..pos]) >= max_width * 2 / 3 {
+            out.truncate(pos);
+        }
+    }
+
+    format!("{}…", out.trim_end())
+}
+
+/// Word-wrap `s` into lines of at most `width` terminal columns, splitting
+/// words that are longer than a whole line.
+fn wrap(s: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in s.split_whitespace() {
+        let needed = if line.is_empty() { 0 } else { 1 } + display_width(word);
+        if !line.is_empty() && display_width(&line) + needed > width {
+            lines.push(std::mem::take(&mut line));
+        }
+
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        for c in word.chars() {
+            let w = c.width().unwrap_or(0);
+            if display_width(&line) + w > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            line.push(c);
+        }
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
     }
+    lines
 }
@@ -1,92 +1,104 @@
+mod activity;
+mod alias;
 mod cli;
 mod commands;
+mod config;
+mod deadline;
 mod display;
+mod project;
+mod reminder;
+mod rules;
+mod select;
 mod store;
 mod time;
 mod todo;
 
 use clap::Parser;
-use cli::{Cli, Command};
-use commands::list::Filter;
+use cli::Cli;
+use config::{Config, Settings};
 use display::DisplayConfig;
+use std::env;
+use std::iter;
 use store::Store;
+use time::TimeOptions;
 
 fn main() {
-    let cli = Cli::parse();
+    let loaded = Config::load().and_then(|c| Ok((c.aliases()?, c)));
 
-    let config = DisplayConfig::new(cli.get_color_mode());
-
-    let mut store = match Store::load() {
+    // User aliases and macros are expanded before clap sees the arguments
+    let args: Vec<String> = env::args().skip(1).collect();
+    let runs = match &loaded {
+        Ok((aliases, _)) => aliases.expand(&args).unwrap_or_else(|e| {
+            display::print_error(&e);
+            std::process::exit(1);
+        }),
+        Err(_) => vec![args],
+    };
+    // Parse every command of a macro before running any of them
+    let clis: Vec<Cli> = runs
+        .into_iter()
+        .map(|args| Cli::parse_from(iter::once("tsk".to_string()).chain(args)))
+        .collect();
+
+    let color_setting = loaded.as_ref().ok().and_then(|(_, c)| c.display.color.force());
+    let display_for = |cli: &Cli| DisplayConfig::new(cli.get_color_mode().or(color_setting), cli.get_output_format());
+    // Report config errors below in the requested output format
+    display_for(&clis[0]);
+
+    let settings = loaded.and_then(|(aliases, c)| {
+        let settings = Settings {
+            aliases,
+            layouts: c.layouts()?,
+            rules: c.tag_rules()?,
+            display: c.display,
+            list: c.list,
+            bulk: c.bulk,
+            reminder_command: c.reminder_command()?,
+        };
+        Ok((settings, c.time_options()?))
+    });
+    let (settings, mut time_options) = match settings {
         Ok(s) => s,
+        // `tsk config` still runs, so a bad value can be fixed with `config set`
+        Err(e) if matches!(clis[0].command, Some(cli::Command::Config { .. })) => {
+            display::print_warning(&format!("Invalid config: {}", e));
+            (Settings::default(), TimeOptions::default())
+        }
         Err(e) => {
-            display::print_error(&format!("Could not load store: {}", e));
+            display::print_error(&format!("Invalid config: {}", e));
             std::process::exit(1);
         }
     };
-
-    match cli.command {
-        Some(Command::Add { text, p, t }) => {
-            commands::add::run(text, p, t, &mut store, &config);
-        }
-
-        Some(Command::Ls) | None => {
-            commands::list::run(&store, &config, cli.sort_by, Filter::Open, &cli.tags, cli.project.as_deref());
-        }
-
-        Some(Command::All) => {
-            commands::list::run(&store, &config, cli.sort_by, Filter::All, &cli.tags, cli.project.as_deref());
-        }
-
-        Some(Command::Today) => {
-            commands::today::run(&store, &config);
-        }
-
-        Some(Command::Week) => {
-            commands::list::run(&store, &config, cli.sort_by, Filter::Week, &cli.tags, cli.project.as_deref());
-        }
-
-        Some(Command::Overdue) => {
-            commands::list::run(&store, &config, cli.sort_by, Filter::Overdue, &cli.tags, cli.project.as_deref());
-        }
-
-        Some(Command::Done { ids }) => {
-            commands::done::run(ids, &mut store, &config);
-        }
-
-        Some(Command::Delete { ids }) => {
-            commands::delete::run(ids, &mut store, &config);
-        }
-
-        Some(Command::Edit {
-            id,
-            text,
-            p,
-            t,
-            clear_time,
-            clear_priority,
-            clear_project,
-        }) => {
-            commands::edit::run(id, text, p, t, clear_time, clear_priority, clear_project, &mut store, &config);
-        }
-
-        Some(Command::Project { name }) => {
-            commands::project::run(&store, &config, &name);
-        }
-
-        Some(Command::Projects) => {
-            commands::project::list_projects(&store, &config);
-        }
-
-        Some(Command::Clear) => {
-            commands::clear::run(&mut store, &config);
+    time_options.display_zone = clis[0].tz;
+    time::set_options(time_options);
+
+    let mut runs = Vec::new();
+    for mut cli in clis {
+        cli.sort_by = cli.sort_by.or(settings.list.sort);
+        match cli.get_layout() {
+            Ok(layout) => runs.push((cli, layout)),
+            Err(e) => {
+                display::print_error(&format!("Invalid template: {}", e));
+                std::process::exit(1);
+            }
         }
+    }
 
-        Some(Command::Undo) => {
-            commands::undo::run(&config);
+    let mut store = match Store::load() {
+        Ok(s) => s,
+        Err(e) => {
+            display::print_error(&format!("Could not load store: {}", e));
+            std::process::exit(1);
         }
+    };
 
-        Some(Command::Stats) => {
-            commands::stats::run(&store, &config);
-        }
+    for (cli, layout_override) in runs {
+        let config = settings
+            .display
+            .display_config(&cli, &settings.layouts, layout_override)
+            .with_confirm_threshold(settings.bulk.confirm_threshold);
+        commands::dispatch(cli, &mut store, &config, &settings);
     }
+
+    std::process::exit(display::exit_code());
 }
@@ -0,0 +1,88 @@
+use crate::deadline::Deadline;
+use chrono::{DateTime, Local};
+use colored::Color;
+use serde::{Deserialize, Serialize};
+
+/// A project record. Tasks refer to projects by name; a record adds
+/// details and keeps the project listed after its last task is done.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Project {
+    pub name: String,
+    pub description: Option<String>,
+    /// Date the project should be finished by
+    pub target: Option<Deadline>,
+    pub status: ProjectStatus,
+    pub color: Option<ProjectColor>,
+    pub created_at: DateTime<Local>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectStatus {
+    Active,
+    Paused,
+    Done,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectColor {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Project {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            description: None,
+            target: None,
+            status: ProjectStatus::Active,
+            color: None,
+            created_at: Local::now(),
+        }
+    }
+
+    pub fn with_description(mut self, description: Option<String>) -> Self {
+        self.description = description;
+        self
+    }
+
+    pub fn with_target(mut self, target: Option<Deadline>) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_color(mut self, color: Option<ProjectColor>) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Display color, defaulting to the usual project magenta.
+    pub fn display_color(&self) -> Color {
+        match self.color {
+            Some(ProjectColor::Red) => Color::Red,
+            Some(ProjectColor::Green) => Color::Green,
+            Some(ProjectColor::Yellow) => Color::Yellow,
+            Some(ProjectColor::Blue) => Color::Blue,
+            Some(ProjectColor::Cyan) => Color::Cyan,
+            Some(ProjectColor::White) => Color::White,
+            Some(ProjectColor::Magenta) | None => Color::Magenta,
+        }
+    }
+}
+
+impl ProjectStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            ProjectStatus::Active => "active",
+            ProjectStatus::Paused => "paused",
+            ProjectStatus::Done => "done",
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::deadline::Deadline;
+use crate::time::{default_time, format_deadline, format_duration, parse_deadline, parse_duration};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// When to be reminded of a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Reminder {
+    /// Minutes before the deadline
+    Before(u32),
+    /// A fixed time
+    At(Deadline),
+}
+
+impl Reminder {
+    /// `15m`, `1h` or `2d` before the deadline, otherwise a time such as
+    /// `tomorrow 9am`. A bare date reminds at the default time (9am).
+    pub fn parse(input: &str) -> Option<Self> {
+        if let Some(offset) = parse_duration(input) {
+            return u32::try_from(offset.num_minutes()).ok().map(Reminder::Before);
+        }
+
+        match parse_deadline(input)? {
+            Deadline::Day(date) => Some(Reminder::At(Deadline::Floating(date.and_time(default_time())))),
+            deadline => Some(Reminder::At(deadline)),
+        }
+    }
+
+    /// When the reminder goes off, or None for an offset on a task without a
+    /// deadline. Offsets from all-day deadlines count from the start of the day.
+    pub fn fire_time(self, deadline: Option<Deadline>) -> Option<DateTime<Utc>> {
+        match self {
+            Reminder::Before(minutes) => {
+                let due = deadline?.in_viewer_zone().with_timezone(&Utc);
+                Some(due - Duration::minutes(minutes as i64))
+            }
+            Reminder::At(at) => Some(at.instant()),
+        }
+    }
+}
+
+/// "15m before" or "tomorrow 9:00am".
+impl fmt::Display for Reminder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reminder::Before(minutes) => write!(f, "{} before", format_duration(*minutes)),
+            Reminder::At(at) => write!(f, "{}", format_deadline(*at, false)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveTime, TimeZone};
+
+    fn day() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 13).unwrap()
+    }
+
+    #[test]
+    fn parses_offsets_and_times() {
+        assert_eq!(Reminder::parse("15m"), Some(Reminder::Before(15)));
+        assert_eq!(Reminder::parse("2d"), Some(Reminder::Before(2 * 24 * 60)));
+        // Too long to store as minutes
+        assert_eq!(Reminder::parse("9999999w"), None);
+        // A bare date reminds at the default time
+        assert_eq!(
+            Reminder::parse("2026-03-13"),
+            Some(Reminder::At(Deadline::Floating(day().and_time(default_time()))))
+        );
+    }
+
+    #[test]
+    fn offset_needs_a_deadline() {
+        assert_eq!(Reminder::Before(15).fire_time(None), None);
+    }
+
+    #[test]
+    fn offset_from_timed_deadline() {
+        let due = Utc.with_ymd_and_hms(2026, 3, 13, 15, 0, 0).unwrap();
+        assert_eq!(
+            Reminder::Before(30).fire_time(Some(Deadline::at(due))),
+            Some(Utc.with_ymd_and_hms(2026, 3, 13, 14, 30, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn offset_from_all_day_deadline_counts_from_start_of_day() {
+        let start = Deadline::Floating(day().and_time(NaiveTime::MIN)).instant();
+        assert_eq!(
+            Reminder::Before(60).fire_time(Some(Deadline::Day(day()))),
+            Some(start - Duration::hours(1))
+        );
+    }
+
+    #[test]
+    fn fixed_time_ignores_deadline() {
+        let at = Utc.with_ymd_and_hms(2026, 3, 12, 9, 0, 0).unwrap();
+        let reminder = Reminder::At(Deadline::at(at));
+        assert_eq!(reminder.fire_time(None), Some(at));
+        assert_eq!(reminder.fire_time(Some(Deadline::Day(day()))), Some(at));
+    }
+}
@@ -0,0 +1,82 @@
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Tagging conventions from the `[tags]` config section, applied when
+/// tasks are added or edited.
+#[derive(Debug, Default)]
+pub struct TagRules {
+    /// Lowercase alias → canonical tag
+    aliases: HashMap<String, String>,
+    /// Lowercase tag → tags it implies
+    implies: HashMap<String, Vec<String>>,
+    patterns: Vec<PatternRule>,
+}
+
+/// Tags and project given to tasks whose text matches a pattern.
+#[derive(Debug)]
+pub struct PatternRule {
+    pub pattern: Regex,
+    pub tags: Vec<String>,
+    pub project: Option<String>,
+}
+
+impl TagRules {
+    pub fn new(
+        aliases: HashMap<String, String>,
+        implies: HashMap<String, Vec<String>>,
+        patterns: Vec<PatternRule>,
+    ) -> Self {
+        let strip = |t: &str| t.trim_start_matches('+').to_string();
+        Self {
+            aliases: aliases
+                .into_iter()
+                .map(|(alias, tag)| (strip(&alias).to_lowercase(), strip(&tag)))
+                .collect(),
+            implies: implies
+                .into_iter()
+                .map(|(tag, implied)| (strip(&tag).to_lowercase(), implied.iter().map(|t| strip(t)).collect()))
+                .collect(),
+            patterns,
+        }
+    }
+
+    /// The canonical spelling of a tag.
+    pub fn resolve_alias(&self, tag: &str) -> String {
+        self.aliases.get(&tag.to_lowercase()).cloned().unwrap_or_else(|| tag.to_string())
+    }
+
+    /// Resolve aliases and add every implied tag, following chains
+    /// (`+bug` → `+work` → `+billable`).
+    pub fn expand(&self, tags: &[String]) -> Vec<String> {
+        let mut result: Vec<String> = Vec::new();
+        let mut pending: Vec<String> = tags.iter().map(|t| self.resolve_alias(t)).collect();
+        pending.reverse();
+
+        while let Some(tag) = pending.pop() {
+            if result.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+                continue;
+            }
+            if let Some(implied) = self.implies.get(&tag.to_lowercase()) {
+                pending.extend(implied.iter().rev().map(|t| self.resolve_alias(t)));
+            }
+            result.push(tag);
+        }
+
+        result
+    }
+
+    /// Tags and the first project from pattern rules matching `text`.
+    pub fn match_text(&self, text: &str) -> (Vec<String>, Option<String>) {
+        let mut tags = Vec::new();
+        let mut project = None;
+
+        for rule in self.patterns.iter().filter(|r| r.pattern.is_match(text)) {
+            tags.extend(rule.tags.iter().map(|t| t.trim_start_matches('+').to_string()));
+            if project.is_none() {
+                project = rule.project.as_ref().map(|p| p.trim_start_matches('@').to_string());
+            }
+        }
+
+        (tags, project)
+    }
+}
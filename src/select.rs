@@ -0,0 +1,124 @@
+use crate::cli::parse_task_refs;
+use crate::display::{confirm, print_error, render_rows, DisplayConfig, View};
+use crate::store::{Store, TaskRef};
+use crate::todo::Todo;
+
+/// Bulk actions touching more tasks than this ask for confirmation, unless
+/// `bulk.confirm_threshold` says otherwise.
+pub const CONFIRM_THRESHOLD: usize = 5;
+
+/// Which tasks a bulk command applies to: explicit IDs, filters, or both.
+#[derive(Debug, Default)]
+pub struct Selector {
+    pub refs: Vec<TaskRef>,
+    pub tags: Vec<String>,
+    pub project: Option<String>,
+    pub overdue: bool,
+}
+
+/// Tasks picked by a selector, plus errors for IDs that did not resolve.
+pub struct Selection {
+    pub ids: Vec<u32>,
+    pub errors: Vec<String>,
+}
+
+impl Selector {
+    /// Build a selector from ID specs (`3`, `1-5,9`, a UUID prefix), `+tag`
+    /// and `@project` tokens, combined with the global `-T`/`-P` filters.
+    pub fn parse(specs: &[String], tags: &[String], project: Option<&str>, overdue: bool) -> Result<Self, String> {
+        let mut selector = Selector {
+            refs: Vec::new(),
+            tags: tags.to_vec(),
+            project: project.map(str::to_string),
+            overdue,
+        };
+
+        for spec in specs {
+            if let Some(tag) = spec.strip_prefix('+').filter(|t| !t.is_empty()) {
+                selector.tags.push(tag.to_string());
+            } else if let Some(project) = spec.strip_prefix('@').filter(|p| !p.is_empty()) {
+                selector.project = Some(project.to_string());
+            } else {
+                selector.refs.extend(parse_task_refs(spec)?);
+            }
+        }
+
+        Ok(selector)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.refs.is_empty() && self.tags.is_empty() && self.project.is_none() && !self.overdue
+    }
+
+    fn matches_filters(&self, todo: &Todo) -> bool {
+        self.tags.iter().all(|tag| todo.has_tag(tag))
+            && self.project.as_ref().map(|p| todo.in_project(p)).unwrap_or(true)
+            && (!self.overdue || todo.is_overdue())
+    }
+
+    /// Resolve against the store. Explicit IDs may name completed tasks;
+    /// filters alone only pick open ones.
+    pub fn resolve(&self, store: &Store) -> Selection {
+        let mut ids = Vec::new();
+        let mut errors = Vec::new();
+
+        if self.refs.is_empty() {
+            ids = store
+                .open_todos()
+                .filter(|t| self.matches_filters(t))
+                .map(|t| t.id)
+                .collect();
+        } else {
+            for task_ref in &self.refs {
+                match store.resolve(task_ref) {
+                    Ok(todo) if self.matches_filters(todo) && !ids.contains(&todo.id) => ids.push(todo.id),
+                    Ok(_) => {}
+                    Err(e) => errors.push(e),
+                }
+            }
+        }
+
+        Selection { ids, errors }
+    }
+}
+
+impl Selection {
+    /// Print errors for unresolved IDs, or "No matching tasks" when nothing
+    /// matched without one. Returns false if there is nothing to act on.
+    pub fn report(&self) -> bool {
+        for e in &self.errors {
+            print_error(e);
+        }
+        if self.ids.is_empty() && self.errors.is_empty() {
+            print_error("No matching tasks");
+        }
+        !self.ids.is_empty()
+    }
+}
+
+/// Preview tasks and ask before a large bulk action. Returns true to proceed.
+pub fn confirm_bulk(action: &str, ids: &[u32], yes: bool, store: &Store, config: &DisplayConfig) -> Result<bool, String> {
+    if yes || ids.len() <= config.confirm_threshold {
+        return Ok(true);
+    }
+
+    if config.is_machine() {
+        return Err(format!("{} tasks match; pass --yes to {} them", ids.len(), action));
+    }
+
+    let todos: Vec<&Todo> = store.todos.iter().filter(|t| ids.contains(&t.id)).collect();
+    for row in render_rows(&todos, View::List, config) {
+        println!("{}", row);
+    }
+
+    let prompt = format!("{} {} tasks?", capitalize(action), ids.len());
+    confirm(&prompt).ok_or_else(|| format!("{} tasks match; pass --yes to {} them", ids.len(), action))
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -1,4 +1,6 @@
-use crate::todo::Todo;
+use crate::project::Project;
+use crate::todo::{is_subproject, Todo};
+use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io;
@@ -9,14 +11,30 @@ pub struct Store {
     pub version: u32,
     pub next_id: u32,
     pub todos: Vec<Todo>,
+    /// Project records; most projects exist only as names on tasks
+    #[serde(default)]
+    pub projects: Vec<Project>,
+}
+
+/// Store format version. Version 2 added per-task UUIDs.
+const STORE_VERSION: u32 = 2;
+
+/// A task reference given on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskRef {
+    /// Numeric handle, e.g. `12`
+    Handle(u32),
+    /// Full UUID or a unique prefix of one
+    Uuid(String),
 }
 
 impl Default for Store {
     fn default() -> Self {
         Self {
-            version: 1,
+            version: STORE_VERSION,
             next_id: 1,
             todos: Vec::new(),
+            projects: Vec::new(),
         }
     }
 }
@@ -30,9 +48,12 @@ impl Store {
         }
 
         let content = fs::read_to_string(&path)?;
-        let store: Store = serde_json::from_str(&content)
+        let mut store: Store = serde_json::from_str(&content)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
 
+        if store.migrate() {
+            store.save()?;
+        }
         Ok(store)
     }
 
@@ -64,11 +85,7 @@ impl Store {
             fs::write(&undo_path, current)?;
         }
 
-        let content = serde_json::to_string_pretty(self)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
-
-        fs::write(&path, content)?;
-        Ok(())
+        self.save()
     }
 
     pub fn undo() -> io::Result<Self> {
@@ -85,18 +102,34 @@ impl Store {
         Self::load()
     }
 
-    fn path() -> io::Result<PathBuf> {
+    /// Directory holding the store and related state (`~/.tsk`).
+    pub fn data_dir() -> io::Result<PathBuf> {
         let home = dirs::home_dir()
             .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Home directory not found"))?;
 
-        Ok(home.join(".tsk").join("todos.json"))
+        Ok(home.join(".tsk"))
+    }
+
+    fn path() -> io::Result<PathBuf> {
+        Ok(Self::data_dir()?.join("todos.json"))
     }
 
     fn undo_path() -> io::Result<PathBuf> {
-        let home = dirs::home_dir()
-            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Home directory not found"))?;
+        Ok(Self::data_dir()?.join("undo.json"))
+    }
+
+    /// Bring an older store up to the current format. Returns true if
+    /// anything changed.
+    fn migrate(&mut self) -> bool {
+        if self.version >= STORE_VERSION {
+            return false;
+        }
 
-        Ok(home.join(".tsk").join("undo.json"))
+        for todo in self.todos.iter_mut().filter(|t| t.uuid.is_empty()) {
+            todo.uuid = Uuid::new_v4().to_string();
+        }
+        self.version = STORE_VERSION;
+        true
     }
 
     pub fn add(&mut self, mut todo: Todo) -> &Todo {
@@ -106,10 +139,114 @@ impl Store {
         self.todos.last().unwrap()
     }
 
+    pub fn get(&self, id: u32) -> Option<&Todo> {
+        self.todos.iter().find(|t| t.id == id)
+    }
+
     pub fn get_mut(&mut self, id: u32) -> Option<&mut Todo> {
         self.todos.iter_mut().find(|t| t.id == id)
     }
 
+    /// Find the task a reference points to.
+    pub fn resolve(&self, task_ref: &TaskRef) -> Result<&Todo, String> {
+        match task_ref {
+            TaskRef::Handle(id) => self
+                .todos
+                .iter()
+                .find(|t| t.id == *id)
+                .ok_or_else(|| format!("Task #{} not found", id)),
+            TaskRef::Uuid(prefix) => {
+                let mut matches = self.todos.iter().filter(|t| t.uuid.starts_with(prefix.as_str()));
+                match (matches.next(), matches.next()) {
+                    (Some(todo), None) => Ok(todo),
+                    (None, _) => Err(format!("Task {} not found", prefix)),
+                    (Some(_), Some(_)) => Err(format!("ID prefix {} matches more than one task", prefix)),
+                }
+            }
+        }
+    }
+
+    /// Reassign handles: open tasks get 1..n in their current order, then
+    /// completed tasks follow. Returns `(old, new)` for each changed handle.
+    pub fn renumber(&mut self) -> Vec<(u32, u32)> {
+        let mut order: Vec<usize> = (0..self.todos.len()).collect();
+        order.sort_by_key(|&i| (self.todos[i].done, self.todos[i].id));
+
+        let mut changes = Vec::new();
+        for (handle, i) in (1..).zip(order) {
+            let todo = &mut self.todos[i];
+            if todo.id != handle {
+                changes.push((todo.id, handle));
+                todo.id = handle;
+            }
+        }
+
+        self.next_id = self.todos.len() as u32 + 1;
+        changes
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.todos.iter().any(|t| t.has_tag(tag))
+    }
+
+    pub fn has_project(&self, project: &str) -> bool {
+        self.todos.iter().any(|t| t.in_project(project)) || self.project(project).is_some()
+    }
+
+    /// The record for exactly this project, if one exists.
+    pub fn project(&self, name: &str) -> Option<&Project> {
+        self.projects.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn project_mut(&mut self, name: &str) -> Option<&mut Project> {
+        self.projects.iter_mut().find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Replace a tag (any capitalization) with another on every task.
+    /// Returns the handles of changed tasks.
+    pub fn rename_tag(&mut self, from: &str, to: &str) -> Vec<u32> {
+        let mut changed = Vec::new();
+        for todo in self.todos.iter_mut().filter(|t| t.has_tag(from)) {
+            let before = todo.clone();
+            todo.remove_tag(from);
+            todo.add_tag(to.to_string());
+            todo.record_changes(&before);
+            changed.push(todo.id);
+        }
+        changed
+    }
+
+    /// Move every task in one project, and its subprojects, to another.
+    /// Returns the handles of changed tasks.
+    pub fn rename_project(&mut self, from: &str, to: &str) -> Vec<u32> {
+        let merging = !from.eq_ignore_ascii_case(to) && self.has_project(to);
+        let mut changed = Vec::new();
+        for todo in self.todos.iter_mut().filter(|t| t.in_project(from)) {
+            // Keep the subproject suffix: client.acme.web → work.acme.web
+            let suffix = todo.project.as_deref().map(|p| p[from.len()..].to_string()).unwrap_or_default();
+            let before = todo.clone();
+            todo.project = Some(format!("{}{}", to, suffix));
+            todo.record_changes(&before);
+            changed.push(todo.id);
+        }
+
+        // Move records along; when merging, the target keeps its own details
+        let (moved, kept): (Vec<Project>, Vec<Project>) =
+            std::mem::take(&mut self.projects).into_iter().partition(|p| is_subproject(&p.name, from));
+        self.projects = kept;
+        for mut record in moved {
+            if merging && record.name.eq_ignore_ascii_case(from) {
+                continue;
+            }
+            record.name = format!("{}{}", to, &record.name[from.len()..]);
+            if self.project(&record.name).is_none() {
+                self.projects.push(record);
+            }
+        }
+
+        changed
+    }
+
     pub fn remove(&mut self, id: u32) -> Option<Todo> {
         if let Some(pos) = self.todos.iter().position(|t| t.id == id) {
             Some(self.todos.remove(pos))
@@ -118,10 +255,10 @@ impl Store {
         }
     }
 
-    pub fn clear_completed(&mut self) -> usize {
-        let before = self.todos.len();
-        self.todos.retain(|t| !t.done);
-        before - self.todos.len()
+    pub fn clear_completed(&mut self) -> Vec<Todo> {
+        let (done, open) = std::mem::take(&mut self.todos).into_iter().partition(|t| t.done);
+        self.todos = open;
+        done
     }
 
     pub fn open_todos(&self) -> impl Iterator<Item = &Todo> {
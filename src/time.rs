@@ -1,191 +1,1168 @@
-use chrono::{DateTime, Datelike, Duration, Local, NaiveTime, TimeZone, Weekday};
+use crate::deadline::Deadline;
+use chrono::{
+    DateTime, Datelike, Days, Duration, FixedOffset, Local, LocalResult, Months, NaiveDate, NaiveDateTime, NaiveTime,
+    Offset as _, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
 
-pub fn parse_time(input: &str) -> Option<DateTime<Local>> {
-    let input = input.trim().to_lowercase();
+/// How to read an expression that leaves the year, week or day open, such
+/// as `1/5`, `friday` or `9am`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Resolution {
+    /// The first reading that is not in the past
+    #[default]
+    Future,
+    /// Whichever reading is closest to now, past or future
+    Nearest,
+    /// As written: this year, today, the coming weekday (today included)
+    Strict,
+    /// The latest reading that is not in the future, for looking back
+    Past,
+}
+
+/// First day of the week.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+}
 
-    // Handle relative times: "in 2 hours", "in 3 days"
-    if input.starts_with("in ") {
-        return parse_relative(&input[3..]);
+impl WeekStart {
+    fn weekday(self) -> Weekday {
+        match self {
+            WeekStart::Monday => Weekday::Mon,
+            WeekStart::Sunday => Weekday::Sun,
+        }
     }
+}
+
+/// What `tsk week` covers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekMode {
+    /// The next seven days
+    #[default]
+    Rolling,
+    /// The rest of the current week
+    Calendar,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum ClockStyle {
+    /// `3:30pm`
+    #[default]
+    #[serde(rename = "12h")]
+    TwelveHour,
+    /// `15:30`
+    #[serde(rename = "24h")]
+    TwentyFourHour,
+}
+
+/// Order of day, month and year in numeric dates, both typed and shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateOrder {
+    /// `12/25/2026`
+    #[default]
+    Mdy,
+    /// `25/12/2026`
+    Dmy,
+    /// `2026/12/25`
+    Ymd,
+}
+
+/// Date parsing settings from the `[dates]` config section.
+#[derive(Debug, Clone)]
+pub struct TimeOptions {
+    pub resolution: Resolution,
+    /// Zone deadlines are shown in (`--tz`); the system zone if unset
+    pub display_zone: Option<Tz>,
+    pub calendar: WorkCalendar,
+    /// Count "this week" in working days rather than calendar days
+    pub business_week: bool,
+    pub week_start: WeekStart,
+    pub week: WeekMode,
+    pub clock: ClockStyle,
+    pub date_order: DateOrder,
+    /// Make dates given without a time all-day deadlines
+    pub all_day: bool,
+    /// Time for a named day without one (`friday`) when not all-day
+    pub default_time: NaiveTime,
+    /// Time for `today`, `eod` and `end of month` when not all-day
+    pub end_of_day: NaiveTime,
+}
 
-    // Handle "today"
-    if input == "today" {
-        return Some(Local::now().date_naive().and_hms_opt(23, 59, 0)?.and_local_timezone(Local).single()?);
+impl Default for TimeOptions {
+    fn default() -> Self {
+        TimeOptions {
+            resolution: Resolution::default(),
+            display_zone: None,
+            calendar: WorkCalendar::default(),
+            business_week: false,
+            week_start: WeekStart::default(),
+            week: WeekMode::default(),
+            clock: ClockStyle::default(),
+            date_order: DateOrder::default(),
+            all_day: true,
+            default_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default(),
+            end_of_day: NaiveTime::from_hms_opt(23, 59, 0).unwrap_or_default(),
+        }
     }
+}
 
-    if input.starts_with("today ") {
-        let time_part = &input[6..];
-        let time = parse_time_only(time_part)?;
-        let date = Local::now().date_naive();
-        return date.and_time(time).and_local_timezone(Local).single();
+/// Working days and holidays, for business-day arithmetic.
+#[derive(Debug, Clone)]
+pub struct WorkCalendar {
+    work_days: Vec<Weekday>,
+    holidays: BTreeSet<NaiveDate>,
+}
+
+impl Default for WorkCalendar {
+    fn default() -> Self {
+        let work_days = vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+        WorkCalendar::new(work_days, BTreeSet::new())
     }
+}
 
-    // Handle "tomorrow"
-    if input == "tomorrow" {
-        return Some(Local::now().date_naive().succ_opt()?.and_hms_opt(9, 0, 0)?.and_local_timezone(Local).single()?);
+impl WorkCalendar {
+    pub fn new(work_days: Vec<Weekday>, holidays: BTreeSet<NaiveDate>) -> Self {
+        WorkCalendar { work_days, holidays }
     }
 
-    if input.starts_with("tomorrow ") {
-        let time_part = &input[9..];
-        let time = parse_time_only(time_part)?;
-        let date = Local::now().date_naive().succ_opt()?;
-        return date.and_time(time).and_local_timezone(Local).single();
+    pub fn is_workday(&self, date: NaiveDate) -> bool {
+        self.work_days.contains(&date.weekday()) && !self.holidays.contains(&date)
     }
 
-    // Handle weekday names: "monday", "fri", "fri 3pm"
-    if let Some(result) = parse_weekday_input(&input) {
-        return Some(result);
+    /// Move `days` working days from `date`, backwards if negative.
+    pub fn add_workdays(&self, date: NaiveDate, days: i64) -> Option<NaiveDate> {
+        if self.work_days.is_empty() {
+            return None;
+        }
+
+        let step = if days < 0 { Duration::days(-1) } else { Duration::days(1) };
+        let mut date = date;
+        for _ in 0..days.abs() {
+            date = date.checked_add_signed(step)?;
+            while !self.is_workday(date) {
+                date = date.checked_add_signed(step)?;
+            }
+        }
+        Some(date)
     }
 
-    // Handle time only: "11am", "3:30pm", "14:00"
-    if let Some(time) = parse_time_only(&input) {
-        let today = Local::now().date_naive();
-        return today.and_time(time).and_local_timezone(Local).single();
+    pub fn work_days(&self) -> &[Weekday] {
+        &self.work_days
     }
 
-    // Handle date formats: "12/25", "2024-12-25"
-    if let Some(result) = parse_date_input(&input) {
-        return Some(result);
+    /// Working days in a week, e.g. 5 for Monday to Friday.
+    pub fn week_length(&self) -> i64 {
+        self.work_days.len() as i64
     }
+}
 
-    None
+static OPTIONS: OnceLock<TimeOptions> = OnceLock::new();
+
+/// Install settings for the rest of the process. Call once at startup.
+pub fn set_options(options: TimeOptions) {
+    let _ = OPTIONS.set(options);
+}
+
+fn options() -> &'static TimeOptions {
+    OPTIONS.get_or_init(TimeOptions::default)
+}
+
+/// Time of day used for a date given without one, e.g. a reminder on `friday`.
+pub fn default_time() -> NaiveTime {
+    options().default_time
+}
+
+/// Parse a deadline. A trailing zone name pins wall times to that zone
+/// (`fri 3pm America/New_York`) and `floating` keeps the wall time with no
+/// zone. Dates without a time are all-day deadlines.
+pub fn parse_deadline(input: &str) -> Option<Deadline> {
+    parse_deadline_at(input, Utc::now())
+}
+
+/// Parse a natural-language deadline relative to `now`.
+///
+/// Accepts offsets (`in 2 weeks 3 days`, `3 days ago`), named days (`today`,
+/// `tomorrow`, `next friday`, `eow`, `end of month`), ordinal weekdays
+/// (`first monday of next month`), calendar dates (`12/25`, `2024-12-25`,
+/// `dec 25th`) and times (`3pm`, `14:00`, `noon`, `midnight`), with a date
+/// and a time in either order, optionally followed by a zone.
+pub fn parse_deadline_at(input: &str, now: DateTime<Utc>) -> Option<Deadline> {
+    parse_deadline_with(input, now, options().resolution)
+}
+
+/// Like `parse_deadline_at`, with an explicit resolution rule.
+pub fn parse_deadline_with(input: &str, now: DateTime<Utc>, resolution: Resolution) -> Option<Deadline> {
+    let (expr, zone) = split_zone(input);
+
+    match zone {
+        Some(ZoneSpec::Named(tz)) => {
+            let (time, date_only) = parse_in(expr, now.with_timezone(&tz), resolution)?;
+            Some(if date_only && options().all_day {
+                Deadline::Day(time.date_naive())
+            } else {
+                Deadline::At {
+                    time: time.with_timezone(&Utc),
+                    zone: Some(tz),
+                }
+            })
+        }
+        Some(ZoneSpec::Floating) => {
+            let (time, date_only) = parse_in(expr, now.with_timezone(&Local), resolution)?;
+            Some(if date_only && options().all_day {
+                Deadline::Day(time.date_naive())
+            } else {
+                Deadline::Floating(time.naive_local())
+            })
+        }
+        None => {
+            let (time, date_only) = parse_in(expr, now.with_timezone(&Local), resolution)?;
+            Some(if date_only && options().all_day {
+                Deadline::Day(time.date_naive())
+            } else {
+                Deadline::at(time.with_timezone(&Utc))
+            })
+        }
+    }
 }
 
-fn parse_relative(input: &str) -> Option<DateTime<Local>> {
-    let parts: Vec<&str> = input.split_whitespace().collect();
-    if parts.len() != 2 {
+/// Parse with wall times read in `now`'s zone. Also returns whether the
+/// input named a date without a time.
+fn parse_in<Z: TimeZone>(input: &str, now: DateTime<Z>, resolution: Resolution) -> Option<(DateTime<Z>, bool)>
+where
+    Z::Offset: Copy,
+{
+    let tokens = tokenize(input);
+    if tokens.is_empty() {
         return None;
     }
 
-    let amount: i64 = parts[0].parse().ok()?;
-    let unit = parts[1];
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+        now,
+        resolution,
+        date_only: false,
+    };
+    let result = parser.expression()?;
 
-    let duration = match unit {
-        "hour" | "hours" | "h" => Duration::hours(amount),
-        "day" | "days" | "d" => Duration::days(amount),
-        "week" | "weeks" | "w" => Duration::weeks(amount),
-        "minute" | "minutes" | "min" | "m" => Duration::minutes(amount),
-        _ => return None,
+    // Trailing words mean we only understood part of the input
+    if parser.pos != tokens.len() {
+        return None;
+    }
+    Some((result, parser.date_only))
+}
+
+/// Move a deadline by an offset such as `2d`, `1w`, `3 business days` or
+/// `1 month`, earlier with a leading `-`. All-day deadlines stay all-day and
+/// zoned ones keep their wall time in that zone.
+pub fn shift_deadline(deadline: Deadline, offset: &str) -> Option<Deadline> {
+    let (forward, offset) = match offset.strip_prefix('-') {
+        Some(rest) => (false, rest),
+        None => (true, offset.strip_prefix('+').unwrap_or(offset)),
+    };
+
+    let tokens = tokenize(offset);
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+        now: Utc::now(),
+        resolution: options().resolution,
+        date_only: false,
+    };
+    let offset = parser.duration()?;
+    if parser.pos != tokens.len() {
+        return None;
+    }
+
+    let shifted = match deadline {
+        Deadline::At { time, zone: Some(tz) } => Deadline::At {
+            time: offset.apply(time.with_timezone(&tz), forward)?.with_timezone(&Utc),
+            zone: Some(tz),
+        },
+        Deadline::At { time, zone: None } => {
+            Deadline::at(offset.apply(time.with_timezone(&Local), forward)?.with_timezone(&Utc))
+        }
+        // Wall-clock arithmetic, so UTC stands in for "no zone"
+        Deadline::Floating(naive) => Deadline::Floating(offset.apply(naive.and_utc(), forward)?.naive_utc()),
+        Deadline::Day(date) => {
+            Deadline::Day(offset.apply(date.and_time(NaiveTime::MIN).and_utc(), forward)?.date_naive())
+        }
     };
+    Some(shifted)
+}
 
-    Some(Local::now() + duration)
+/// A zone named at the end of a deadline expression.
+enum ZoneSpec {
+    Named(Tz),
+    Floating,
 }
 
-fn parse_time_only(input: &str) -> Option<NaiveTime> {
-    let input = input.trim().to_lowercase();
+fn split_zone(input: &str) -> (&str, Option<ZoneSpec>) {
+    let Some((rest, last)) = input.trim_end().rsplit_once(char::is_whitespace) else {
+        return (input, None);
+    };
 
-    // Handle "3pm", "11am"
-    if input.ends_with("am") || input.ends_with("pm") {
-        let is_pm = input.ends_with("pm");
-        let time_str = &input[..input.len() - 2];
+    if last.eq_ignore_ascii_case("floating") {
+        return (rest, Some(ZoneSpec::Floating));
+    }
+    match parse_zone(last) {
+        Some(tz) => (rest, Some(ZoneSpec::Named(tz))),
+        None => (input, None),
+    }
+}
 
-        if let Some((hour, minute)) = parse_hour_minute(time_str) {
-            let hour = if is_pm && hour != 12 {
-                hour + 12
-            } else if !is_pm && hour == 12 {
-                0
-            } else {
-                hour
+/// Look up an IANA zone name, ignoring case: `America/New_York`, `utc`.
+pub fn parse_zone(name: &str) -> Option<Tz> {
+    chrono_tz::TZ_VARIANTS.iter().find(|tz| tz.name().eq_ignore_ascii_case(name)).copied()
+}
+
+/// Show a moment in the viewer's zone (`--tz`, or the system zone).
+pub fn to_viewer(time: DateTime<Utc>) -> DateTime<FixedOffset> {
+    match options().display_zone {
+        Some(tz) => time.with_timezone(&tz).fixed_offset(),
+        None => time.with_timezone(&Local).fixed_offset(),
+    }
+}
+
+/// Read a wall-clock time in the viewer's zone.
+pub fn from_viewer(naive: NaiveDateTime) -> Option<DateTime<FixedOffset>> {
+    match options().display_zone {
+        Some(tz) => localize(&tz, naive).map(|t| t.fixed_offset()),
+        None => localize(&Local, naive).map(|t| t.fixed_offset()),
+    }
+}
+
+pub fn viewer_now() -> DateTime<FixedOffset> {
+    to_viewer(Utc::now())
+}
+
+/// Lowercase words, with commas and filler words dropped.
+fn tokenize(input: &str) -> Vec<String> {
+    input
+        .to_lowercase()
+        .replace(',', " ")
+        .split_whitespace()
+        .filter(|w| !matches!(*w, "on" | "the" | "and"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// A calendar offset. Months, days and working days vary in length, so they
+/// are kept apart and applied to the wall-clock date.
+#[derive(Default)]
+struct Offset {
+    months: u32,
+    days: u64,
+    duration: Duration,
+    business_days: i64,
+}
+
+impl Offset {
+    fn apply<Z: TimeZone>(&self, dt: DateTime<Z>, forward: bool) -> Option<DateTime<Z>> {
+        let (months, days) = (Months::new(self.months), Days::new(self.days));
+        let shifted = if forward {
+            dt.checked_add_months(months)?.checked_add_days(days)?.checked_add_signed(self.duration)?
+        } else {
+            dt.checked_sub_months(months)?.checked_sub_days(days)?.checked_sub_signed(self.duration)?
+        };
+
+        if self.business_days == 0 {
+            return Some(shifted);
+        }
+        let days = if forward { self.business_days } else { -self.business_days };
+        let date = options().calendar.add_workdays(shifted.date_naive(), days)?;
+        localize(&shifted.timezone(), date.and_time(shifted.time()))
+    }
+
+    fn has_clock_part(&self) -> bool {
+        self.duration.num_seconds() % 86_400 != 0
+    }
+}
+
+/// A time of day. `midnight` means the end of the day, i.e. 00:00 of the next.
+struct Clock {
+    time: NaiveTime,
+    next_day: bool,
+}
+
+/// Which part of a parsed date was left open and may be moved to fit the
+/// resolution rule.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Open {
+    /// Fully specified
+    Nothing,
+    /// `12/25`, `dec 25`: any year
+    Year,
+    /// `friday`: any week
+    Week,
+}
+
+struct Parser<'a, Z: TimeZone> {
+    tokens: &'a [String],
+    pos: usize,
+    now: DateTime<Z>,
+    resolution: Resolution,
+    /// Set when the expression named a date but no time of day
+    date_only: bool,
+}
+
+impl<Z: TimeZone> Parser<'_, Z>
+where
+    Z::Offset: Copy,
+{
+    fn peek(&self) -> Option<&str> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&str> {
+        self.tokens.get(self.pos + offset).map(String::as_str)
+    }
+
+    fn eat(&mut self, word: &str) -> bool {
+        if self.peek() == Some(word) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn today(&self) -> NaiveDate {
+        self.now.date_naive()
+    }
+
+    fn expression(&mut self) -> Option<DateTime<Z>> {
+        if self.eat("now") {
+            return Some(self.now);
+        }
+        if let Some(dt) = self.relative() {
+            return Some(dt);
+        }
+        self.anchored()
+    }
+
+    /// `in <duration> [time]`, `<duration> ago`, `<duration> from now`.
+    fn relative(&mut self) -> Option<DateTime<Z>> {
+        let start = self.pos;
+
+        let (offset, forward) = if self.eat("in") {
+            match self.duration() {
+                Some(offset) => (offset, true),
+                None => {
+                    self.pos = start;
+                    return None;
+                }
+            }
+        } else {
+            match self.duration() {
+                Some(offset) if self.eat("ago") => (offset, false),
+                Some(offset) if self.peek() == Some("from") && self.peek_at(1) == Some("now") => {
+                    self.pos += 2;
+                    (offset, true)
+                }
+                Some(offset) if self.eat("later") => (offset, true),
+                _ => {
+                    self.pos = start;
+                    return None;
+                }
+            }
+        };
+
+        let shifted = offset.apply(self.now, forward)?;
+
+        // "in 2 days at 3pm" pins the time on the shifted day
+        if !offset.has_clock_part() {
+            if let Some(clock) = self.clock() {
+                return self.combine(shifted.date_naive(), &clock);
+            }
+        }
+        Some(shifted)
+    }
+
+    /// One or more `<n> <unit>` pairs, or compact forms such as `1h30m`.
+    fn duration(&mut self) -> Option<Offset> {
+        let mut offset = Offset::default();
+        let mut parsed = false;
+
+        while let Some(word) = self.peek() {
+            let amount: i64 = match word {
+                "a" | "an" => 1,
+                w if w.chars().all(|c| c.is_ascii_digit()) => w.parse().ok()?,
+                w => match parse_duration(w) {
+                    Some(d) if d.num_seconds() % 86_400 == 0 => {
+                        offset.days = offset.days.checked_add(u64::try_from(d.num_days()).ok()?)?;
+                        parsed = true;
+                        self.pos += 1;
+                        continue;
+                    }
+                    Some(d) => {
+                        offset.duration = offset.duration.checked_add(&d)?;
+                        parsed = true;
+                        self.pos += 1;
+                        continue;
+                    }
+                    None => break,
+                },
             };
-            return NaiveTime::from_hms_opt(hour, minute, 0);
+
+            let Some(unit) = self.peek_at(1) else {
+                break;
+            };
+
+            // "3 business days", "2 working days", "5 workdays"
+            let business = match (unit, self.peek_at(2)) {
+                ("workday" | "workdays", _) => Some(2),
+                ("business" | "working", Some("day" | "days")) => Some(3),
+                _ => None,
+            };
+            if let Some(len) = business {
+                offset.business_days = offset.business_days.checked_add(amount)?;
+                self.pos += len;
+                parsed = true;
+                continue;
+            }
+
+            // Out-of-range amounts fail the parse rather than overflow
+            match unit {
+                "minute" | "minutes" | "min" | "mins" | "m" => {
+                    offset.duration = offset.duration.checked_add(&Duration::try_minutes(amount)?)?
+                }
+                "hour" | "hours" | "hr" | "hrs" | "h" => {
+                    offset.duration = offset.duration.checked_add(&Duration::try_hours(amount)?)?
+                }
+                "day" | "days" | "d" => offset.days = offset.days.checked_add(u64::try_from(amount).ok()?)?,
+                "week" | "weeks" | "wk" | "wks" | "w" => {
+                    offset.days = offset.days.checked_add(u64::try_from(amount).ok()?.checked_mul(7)?)?
+                }
+                "month" | "months" | "mo" => offset.months = offset.months.checked_add(u32::try_from(amount).ok()?)?,
+                "year" | "years" | "yr" | "yrs" | "y" => {
+                    offset.months = offset.months.checked_add(u32::try_from(amount).ok()?.checked_mul(12)?)?
+                }
+                _ => break,
+            }
+            self.pos += 2;
+            parsed = true;
         }
+
+        parsed.then_some(offset)
     }
 
-    // Handle "14:00", "9:30"
-    if input.contains(':') {
-        let parts: Vec<&str> = input.split(':').collect();
-        if parts.len() == 2 {
-            let hour: u32 = parts[0].parse().ok()?;
-            let minute: u32 = parts[1].parse().ok()?;
-            return NaiveTime::from_hms_opt(hour, minute, 0);
+    /// A date and a time of day, each optional, in either order.
+    fn anchored(&mut self) -> Option<DateTime<Z>> {
+        let mut date = None;
+        let mut clock = None;
+
+        loop {
+            if date.is_none() {
+                if let Some(d) = self.date() {
+                    date = Some(d);
+                    continue;
+                }
+            }
+            if clock.is_none() {
+                if let Some(c) = self.clock() {
+                    clock = Some(c);
+                    continue;
+                }
+            }
+            break;
+        }
+
+        match (date, clock) {
+            (Some((date, default, open)), clock) => {
+                self.date_only = clock.is_none();
+                let clock = clock.unwrap_or(Clock {
+                    time: default,
+                    next_day: false,
+                });
+                let dates: Vec<NaiveDate> = match open {
+                    Open::Nothing => vec![date],
+                    Open::Year => (-1..=1).filter_map(|y| date.with_year(date.year() + y)).collect(),
+                    Open::Week => (-1..=1).map(|w| date + Duration::weeks(w)).collect(),
+                };
+                let candidates: Vec<_> = dates.iter().filter_map(|d| self.combine(*d, &clock)).collect();
+                let literal = self.combine(date, &clock)?;
+                self.choose(&candidates, literal, true)
+            }
+            (None, Some(clock)) => {
+                // A bare time could be yesterday, today or tomorrow
+                let today = self.today();
+                let dates = [today.pred_opt()?, today, today.succ_opt()?];
+                let candidates: Vec<_> = dates.iter().filter_map(|d| self.combine(*d, &clock)).collect();
+                let literal = self.combine(today, &clock)?;
+                self.choose(&candidates, literal, false)
+            }
+            (None, None) => None,
         }
     }
 
-    None
+    fn combine(&self, date: NaiveDate, clock: &Clock) -> Option<DateTime<Z>> {
+        let date = if clock.next_day { date.succ_opt()? } else { date };
+        localize(&self.now.timezone(), date.and_time(clock.time))
+    }
+
+    /// Pick a reading per the resolution rule. `by_date` compares whole
+    /// days, so `friday` on a Friday afternoon still means today.
+    fn choose(&self, candidates: &[DateTime<Z>], literal: DateTime<Z>, by_date: bool) -> Option<DateTime<Z>> {
+        let today = self.today();
+        let distance = |c: &DateTime<Z>| {
+            if by_date {
+                (c.date_naive() - today).num_days()
+            } else {
+                (*c - self.now).num_seconds()
+            }
+        };
+
+        match self.resolution {
+            Resolution::Strict => Some(literal),
+            Resolution::Future => candidates.iter().find(|c| distance(c) >= 0).or(candidates.last()).copied(),
+            // Ties go to the future reading
+            Resolution::Nearest => candidates.iter().min_by_key(|c| (distance(c).abs(), distance(c) < 0)).copied(),
+            Resolution::Past => candidates.iter().rev().find(|c| distance(c) <= 0).or(candidates.first()).copied(),
+        }
+    }
+
+    /// A calendar day, the time of day to use if none is given, and which
+    /// part was left open.
+    fn date(&mut self) -> Option<(NaiveDate, NaiveTime, Open)> {
+        let start = self.pos;
+        let result = self.date_inner();
+        if result.is_none() {
+            self.pos = start;
+        }
+        result
+    }
+
+    fn date_inner(&mut self) -> Option<(NaiveDate, NaiveTime, Open)> {
+        let today = self.today();
+        let morning = options().default_time;
+        let end_of_day = options().end_of_day;
+        let word = self.peek()?.to_string();
+        self.pos += 1;
+
+        match word.as_str() {
+            "today" => Some((today, end_of_day, Open::Nothing)),
+            "tonight" => Some((today, NaiveTime::from_hms_opt(20, 0, 0)?, Open::Nothing)),
+            "tomorrow" | "tmrw" | "tmr" => Some((today.succ_opt()?, morning, Open::Nothing)),
+            "yesterday" => Some((today.pred_opt()?, morning, Open::Nothing)),
+            "eod" => Some((today, end_of_day, Open::Nothing)),
+            "eow" => Some((end_of(today, Period::Week)?, end_of_day, Open::Nothing)),
+            "eom" => Some((end_of(today, Period::Month)?, end_of_day, Open::Nothing)),
+            "eoy" => Some((end_of(today, Period::Year)?, end_of_day, Open::Nothing)),
+            "end" => {
+                if !self.eat("of") {
+                    return None;
+                }
+                let (reference, period) = self.period_ref()?;
+                Some((end_of(reference, period)?, end_of_day, Open::Nothing))
+            }
+            w if parse_ordinal(w).is_some() && self.peek_at(1) == Some("of") => {
+                // "first monday of next month", "last friday of the month"
+                let ordinal = parse_ordinal(w)?;
+                let weekday = parse_weekday(self.peek()?)?;
+                self.pos += 2;
+                let month = self.month_ref()?;
+                Some((nth_weekday(month, weekday, ordinal)?, morning, Open::Nothing))
+            }
+            "next" | "this" | "last" => {
+                // "next workday", "last business day"
+                let business = match (self.peek(), self.peek_at(1)) {
+                    (Some("workday" | "weekday"), _) => Some(1),
+                    (Some("business" | "working"), Some("day")) => Some(2),
+                    _ => None,
+                };
+                if let Some(len) = business {
+                    self.pos += len;
+                    let calendar = &options().calendar;
+                    let date = match word.as_str() {
+                        "next" => calendar.add_workdays(today, 1)?,
+                        "this" if calendar.is_workday(today) => today,
+                        "this" => calendar.add_workdays(today, 1)?,
+                        _ => calendar.add_workdays(today, -1)?,
+                    };
+                    return Some((date, morning, Open::Nothing));
+                }
+
+                let next = self.peek()?.to_string();
+                if let Some(weekday) = parse_weekday(&next) {
+                    self.pos += 1;
+                    let date = match word.as_str() {
+                        "next" => {
+                            let week_start = options().week_start;
+                            start_of_week(today, week_start) + Duration::days(7 + days_into_week(weekday, week_start))
+                        }
+                        "this" => upcoming(today, weekday, true),
+                        _ => previous(today, weekday),
+                    };
+                    return Some((date, morning, Open::Nothing));
+                }
+                self.pos -= 1;
+                let (reference, period) = self.period_ref()?;
+                Some((start_of(reference, period)?, morning, Open::Nothing))
+            }
+            w if parse_weekday(w).is_some() => Some((upcoming(today, parse_weekday(w)?, true), morning, Open::Week)),
+            w if parse_month(w).is_some() => {
+                // "dec 25", "december 25th 2025"
+                let month = parse_month(w)?;
+                let day = parse_day(self.peek()?)?;
+                self.pos += 1;
+                Some(self.with_year(month, day, morning)?)
+            }
+            w if parse_day(w).is_some() && self.peek().and_then(parse_month).is_some() => {
+                // "25 dec", "25th december 2025"
+                let day = parse_day(w)?;
+                let month = parse_month(self.peek()?)?;
+                self.pos += 1;
+                Some(self.with_year(month, day, morning)?)
+            }
+            w => {
+                let (date, has_year) = parse_numeric_date(w, today.year(), options().date_order)?;
+                Some((date, morning, if has_year { Open::Nothing } else { Open::Year }))
+            }
+        }
+    }
+
+    /// A month and day, with an optional year following.
+    fn with_year(&mut self, month: u32, day: u32, time: NaiveTime) -> Option<(NaiveDate, NaiveTime, Open)> {
+        match self.year() {
+            Some(year) => Some((NaiveDate::from_ymd_opt(year, month, day)?, time, Open::Nothing)),
+            None => Some((NaiveDate::from_ymd_opt(self.today().year(), month, day)?, time, Open::Year)),
+        }
+    }
+
+    /// `[this|next|last] week|month|year`, resolved to a day inside it.
+    fn period_ref(&mut self) -> Option<(NaiveDate, Period)> {
+        let today = self.today();
+        let shift: i32 = if self.eat("next") {
+            1
+        } else if self.eat("last") {
+            -1
+        } else {
+            self.eat("this");
+            0
+        };
+
+        let period = match self.peek()? {
+            "day" => Period::Day,
+            "week" => Period::Week,
+            "month" => Period::Month,
+            "year" => Period::Year,
+            _ => return None,
+        };
+        self.pos += 1;
+
+        let reference = match period {
+            Period::Day => today + Duration::days(shift as i64),
+            Period::Week => today + Duration::weeks(shift as i64),
+            Period::Month => shift_months(today, shift)?,
+            Period::Year => shift_months(today, shift * 12)?,
+        };
+        Some((reference, period))
+    }
+
+    /// `this month`, `next month`, `march`, `march 2027`: the first of that month.
+    fn month_ref(&mut self) -> Option<NaiveDate> {
+        let today = self.today();
+        if let Some(month) = self.peek().and_then(parse_month) {
+            self.pos += 1;
+            let year = self.year().unwrap_or(today.year());
+            return NaiveDate::from_ymd_opt(year, month, 1);
+        }
+
+        let (reference, period) = self.period_ref()?;
+        match period {
+            Period::Month => start_of(reference, Period::Month),
+            _ => None,
+        }
+    }
+
+    fn year(&mut self) -> Option<i32> {
+        let word = self.peek()?;
+        if word.len() != 4 {
+            return None;
+        }
+        let year = word.parse().ok()?;
+        self.pos += 1;
+        Some(year)
+    }
+
+    /// `3pm`, `3:30pm`, `3 pm`, `14:00`, `noon`, `midnight`, `at 15`.
+    fn clock(&mut self) -> Option<Clock> {
+        let start = self.pos;
+        let at = self.eat("at");
+
+        let result = match self.peek() {
+            Some("noon") | Some("midday") => Some(Clock {
+                time: NaiveTime::from_hms_opt(12, 0, 0)?,
+                next_day: false,
+            }),
+            Some("midnight") => Some(Clock {
+                time: NaiveTime::MIN,
+                next_day: true,
+            }),
+            Some(word) => {
+                let word = word.to_string();
+                let meridiem = self.peek_at(1).filter(|w| *w == "am" || *w == "pm").map(str::to_string);
+                match meridiem {
+                    Some(m) if parse_hour_minute(&word).is_some() => {
+                        self.pos += 1;
+                        parse_clock_time(&format!("{}{}", word, m), false)
+                    }
+                    _ => parse_clock_time(&word, at),
+                }
+                .map(|time| Clock { time, next_day: false })
+            }
+            None => None,
+        };
+
+        match result {
+            Some(clock) => {
+                self.pos += 1;
+                Some(clock)
+            }
+            None => {
+                self.pos = start;
+                None
+            }
+        }
+    }
 }
 
-fn parse_hour_minute(input: &str) -> Option<(u32, u32)> {
-    if input.contains(':') {
-        let parts: Vec<&str> = input.split(':').collect();
-        if parts.len() == 2 {
-            let hour: u32 = parts[0].parse().ok()?;
-            let minute: u32 = parts[1].parse().ok()?;
-            return Some((hour, minute));
+#[derive(Clone, Copy)]
+enum Period {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// Resolve a local wall-clock time across DST changes. A time that occurs
+/// twice (clocks going back) takes the first occurrence; a time skipped by
+/// clocks going forward moves forward by the size of the gap, so 2:30 in a
+/// one-hour gap becomes 3:30.
+pub fn localize<Z: TimeZone>(zone: &Z, naive: NaiveDateTime) -> Option<DateTime<Z>> {
+    match zone.from_local_datetime(&naive) {
+        LocalResult::Single(dt) => Some(dt),
+        LocalResult::Ambiguous(a, b) => Some(a.min(b)),
+        LocalResult::None => {
+            // Read the wall time with the offset in force just before the gap
+            let before = zone.from_local_datetime(&(naive - Duration::days(1))).earliest()?;
+            let offset = before.offset().fix();
+            let utc = naive - Duration::seconds(offset.local_minus_utc() as i64);
+            Some(zone.from_utc_datetime(&utc))
         }
+    }
+}
+
+/// The first day of the week containing `date`, counting from `week_start`.
+fn start_of_week(date: NaiveDate, week_start: WeekStart) -> NaiveDate {
+    date - Duration::days(days_into_week(date.weekday(), week_start))
+}
+
+fn days_into_week(weekday: Weekday, week_start: WeekStart) -> i64 {
+    weekday.days_since(week_start.weekday()) as i64
+}
+
+fn start_of(date: NaiveDate, period: Period) -> Option<NaiveDate> {
+    match period {
+        Period::Day => Some(date),
+        Period::Week => Some(start_of_week(date, options().week_start)),
+        Period::Month => NaiveDate::from_ymd_opt(date.year(), date.month(), 1),
+        Period::Year => NaiveDate::from_ymd_opt(date.year(), 1, 1),
+    }
+}
+
+fn end_of(date: NaiveDate, period: Period) -> Option<NaiveDate> {
+    match period {
+        Period::Day => Some(date),
+        Period::Week => Some(start_of_week(date, options().week_start) + Duration::days(6)),
+        Period::Month => shift_months(start_of(date, Period::Month)?, 1)?.pred_opt(),
+        Period::Year => NaiveDate::from_ymd_opt(date.year(), 12, 31),
+    }
+}
+
+fn shift_months(date: NaiveDate, months: i32) -> Option<NaiveDate> {
+    if months >= 0 {
+        date.checked_add_months(Months::new(months as u32))
     } else {
-        let hour: u32 = input.parse().ok()?;
-        return Some((hour, 0));
+        date.checked_sub_months(Months::new(months.unsigned_abs()))
     }
-    None
 }
 
-fn parse_weekday_input(input: &str) -> Option<DateTime<Local>> {
-    let parts: Vec<&str> = input.split_whitespace().collect();
-    let weekday_str = parts[0];
+/// Next `weekday` after `today`, or today itself if `inclusive`.
+fn upcoming(today: NaiveDate, weekday: Weekday, inclusive: bool) -> NaiveDate {
+    let days = (weekday.num_days_from_monday() as i64 - today.weekday().num_days_from_monday() as i64 + 7) % 7;
+    let days = if days == 0 && !inclusive { 7 } else { days };
+    today + Duration::days(days)
+}
+
+/// Most recent `weekday` before `today`.
+fn previous(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let days = (today.weekday().num_days_from_monday() as i64 - weekday.num_days_from_monday() as i64 + 7) % 7;
+    today - Duration::days(if days == 0 { 7 } else { days })
+}
+
+/// The nth (or, for 0, the last) `weekday` of the month starting at `first`.
+fn nth_weekday(first: NaiveDate, weekday: Weekday, n: u8) -> Option<NaiveDate> {
+    if n == 0 {
+        let last = end_of(first, Period::Month)?;
+        let back = (last.weekday().num_days_from_monday() as i64 - weekday.num_days_from_monday() as i64 + 7) % 7;
+        return Some(last - Duration::days(back));
+    }
+
+    let date = upcoming(first, weekday, true) + Duration::weeks(n as i64 - 1);
+    (date.month() == first.month()).then_some(date)
+}
+
+pub fn parse_weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" | "thurs" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
 
-    let weekday = match weekday_str {
-        "monday" | "mon" => Weekday::Mon,
-        "tuesday" | "tue" => Weekday::Tue,
-        "wednesday" | "wed" => Weekday::Wed,
-        "thursday" | "thu" => Weekday::Thu,
-        "friday" | "fri" => Weekday::Fri,
-        "saturday" | "sat" => Weekday::Sat,
-        "sunday" | "sun" => Weekday::Sun,
+fn parse_month(word: &str) -> Option<u32> {
+    let month = match word {
+        "january" | "jan" => 1,
+        "february" | "feb" => 2,
+        "march" | "mar" => 3,
+        "april" | "apr" => 4,
+        "may" => 5,
+        "june" | "jun" => 6,
+        "july" | "jul" => 7,
+        "august" | "aug" => 8,
+        "september" | "sep" | "sept" => 9,
+        "october" | "oct" => 10,
+        "november" | "nov" => 11,
+        "december" | "dec" => 12,
         _ => return None,
     };
+    Some(month)
+}
 
-    let time = if parts.len() > 1 {
-        parse_time_only(parts[1])?
-    } else {
-        NaiveTime::from_hms_opt(9, 0, 0)?
+/// `first`..`fifth` or `1st`..`5th`; `last` is 0.
+fn parse_ordinal(word: &str) -> Option<u8> {
+    match word {
+        "first" | "1st" => Some(1),
+        "second" | "2nd" => Some(2),
+        "third" | "3rd" => Some(3),
+        "fourth" | "4th" => Some(4),
+        "fifth" | "5th" => Some(5),
+        "last" => Some(0),
+        _ => None,
+    }
+}
+
+/// Day of month, with an optional ordinal suffix: `25`, `25th`, `1st`.
+fn parse_day(word: &str) -> Option<u32> {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let suffix = &word[digits.len()..];
+    if !matches!(suffix, "" | "st" | "nd" | "rd" | "th") {
+        return None;
+    }
+    let day: u32 = digits.parse().ok()?;
+    (1..=31).contains(&day).then_some(day)
+}
+
+/// `12/25`, `12-25`, `12/25/2025`, `12/25/25`, `2024-12-25`, `2024/12/25`.
+/// Also returns whether a year was given.
+fn parse_numeric_date(word: &str, current_year: i32, order: DateOrder) -> Option<(NaiveDate, bool)> {
+    let sep = if word.contains('/') { '/' } else { '-' };
+    let parts: Vec<&str> = word.split(sep).collect();
+    let numbers: Vec<u32> = parts.iter().map(|p| p.parse().ok()).collect::<Option<_>>()?;
+    let full_year = |year: u32, digits: usize| match digits {
+        4 => Some(year as i32),
+        2 => Some(2000 + year as i32),
+        _ => None,
     };
 
-    let today = Local::now().date_naive();
-    let today_weekday = today.weekday();
+    // A four-digit year first is always year-month-day
+    let (year, month, day) = match (parts.as_slice(), numbers.as_slice(), order) {
+        ([y, _, _], &[year, month, day], _) if y.len() == 4 => (Some(year as i32), month, day),
+        ([y, _, _], &[year, month, day], DateOrder::Ymd) => (Some(full_year(year, y.len())?), month, day),
+        ([_, _, y], &[day, month, year], DateOrder::Dmy) => (Some(full_year(year, y.len())?), month, day),
+        ([_, _, y], &[month, day, year], DateOrder::Mdy) => (Some(full_year(year, y.len())?), month, day),
+        ([_, _], &[day, month], DateOrder::Dmy) => (None, month, day),
+        ([_, _], &[month, day], _) => (None, month, day),
+        _ => return None,
+    };
 
-    let days_until = (weekday.num_days_from_monday() as i64 - today_weekday.num_days_from_monday() as i64 + 7) % 7;
-    let days_until = if days_until == 0 { 7 } else { days_until };
+    let date = NaiveDate::from_ymd_opt(year.unwrap_or(current_year), month, day)?;
+    Some((date, year.is_some()))
+}
 
-    let target_date = today + Duration::days(days_until);
-    target_date.and_time(time).and_local_timezone(Local).single()
+/// A time of day from the config file: `9am`, `9:00` or `17:30`.
+pub fn parse_time_of_day(input: &str) -> Option<NaiveTime> {
+    parse_clock_time(&input.trim().to_lowercase(), false)
 }
 
-fn parse_date_input(input: &str) -> Option<DateTime<Local>> {
-    let now = Local::now();
+/// A clock time: `3pm`, `3:30pm`, `14:00`, or a bare hour if `bare_hour`.
+fn parse_clock_time(word: &str, bare_hour: bool) -> Option<NaiveTime> {
+    if let Some(time_str) = word.strip_suffix("am").or_else(|| word.strip_suffix("pm")) {
+        let is_pm = word.ends_with("pm");
+        let (hour, minute) = parse_hour_minute(time_str)?;
+        if !(1..=12).contains(&hour) {
+            return None;
+        }
+        let hour = match (is_pm, hour) {
+            (true, 12) => 12,
+            (true, h) => h + 12,
+            (false, 12) => 0,
+            (false, h) => h,
+        };
+        return NaiveTime::from_hms_opt(hour, minute, 0);
+    }
+
+    if word.contains(':') || bare_hour {
+        let (hour, minute) = parse_hour_minute(word)?;
+        return NaiveTime::from_hms_opt(hour, minute, 0);
+    }
+
+    None
+}
+
+fn parse_hour_minute(input: &str) -> Option<(u32, u32)> {
+    match input.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => Some((hour.parse().ok()?, minute.parse().ok()?)),
+        Some(_) => None,
+        None => Some((input.parse().ok()?, 0)),
+    }
+}
+
+/// Read holidays from an ICS calendar (each event's days, end exclusive for
+/// all-day events) or from a list of `YYYY-MM-DD` dates, one per line, with
+/// anything after the date or a `#` ignored.
+pub fn parse_holidays(content: &str) -> Result<BTreeSet<NaiveDate>, String> {
+    if content.trim_start().starts_with("BEGIN:VCALENDAR") {
+        return parse_ics_holidays(content);
+    }
+
+    let mut holidays = BTreeSet::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("");
+        let Some(word) = line.split_whitespace().next() else {
+            continue;
+        };
+        let date = NaiveDate::parse_from_str(word, "%Y-%m-%d")
+            .map_err(|_| format!("line {}: invalid date \"{}\"", i + 1, word))?;
+        holidays.insert(date);
+    }
+    Ok(holidays)
+}
 
-    // Handle "12/25" or "12-25"
-    if input.len() <= 5 && (input.contains('/') || input.contains('-')) {
-        let sep = if input.contains('/') { '/' } else { '-' };
-        let parts: Vec<&str> = input.split(sep).collect();
-        if parts.len() == 2 {
-            let month: u32 = parts[0].parse().ok()?;
-            let day: u32 = parts[1].parse().ok()?;
-            let date = Local.with_ymd_and_hms(now.year(), month, day, 9, 0, 0).single()?;
-            return Some(date);
+fn parse_ics_holidays(content: &str) -> Result<BTreeSet<NaiveDate>, String> {
+    // "DTSTART;VALUE=DATE:20261225" or "DTEND:20261225T170000Z"
+    let ics_date = |line: &str| {
+        let value = line.rsplit(':').next()?.trim();
+        let date = NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()?;
+        Some((date, value.contains('T')))
+    };
+
+    let mut holidays = BTreeSet::new();
+    let mut start = None;
+    let mut end = None;
+    for (i, line) in content.lines().enumerate() {
+        let invalid = || format!("line {}: invalid date \"{}\"", i + 1, line.trim());
+        if line.starts_with("BEGIN:VEVENT") {
+            (start, end) = (None, None);
+        } else if line.starts_with("DTSTART") {
+            start = Some(ics_date(line).ok_or_else(invalid)?.0);
+        } else if line.starts_with("DTEND") {
+            // All-day events end the day before DTEND; timed ones on it
+            let (date, timed) = ics_date(line).ok_or_else(invalid)?;
+            end = if timed { date.succ_opt() } else { Some(date) };
+        } else if line.starts_with("END:VEVENT") {
+            let Some(first) = start.take() else {
+                continue;
+            };
+            let last = end.take().and_then(|d| d.pred_opt()).unwrap_or(first).max(first);
+            holidays.extend(first.iter_days().take_while(|d| *d <= last));
         }
     }
+    Ok(holidays)
+}
 
-    // Handle "2024-12-25"
-    if input.len() == 10 && input.chars().filter(|c| *c == '-').count() == 2 {
-        let parts: Vec<&str> = input.split('-').collect();
-        if parts.len() == 3 {
-            let year: i32 = parts[0].parse().ok()?;
-            let month: u32 = parts[1].parse().ok()?;
-            let day: u32 = parts[2].parse().ok()?;
-            return Local.with_ymd_and_hms(year, month, day, 9, 0, 0).single();
+/// Parse a compact duration such as `45m`, `2h`, `1h30m` or `1d`.
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return None;
+    }
+
+    let mut total = Duration::zero();
+    let mut digits = String::new();
+
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
         }
+
+        let amount: i64 = digits.parse().ok()?;
+        digits.clear();
+
+        let part = match c {
+            'w' => Duration::try_weeks(amount)?,
+            'd' => Duration::try_days(amount)?,
+            'h' => Duration::try_hours(amount)?,
+            'm' => Duration::try_minutes(amount)?,
+            _ => return None,
+        };
+        total = total.checked_add(&part)?;
     }
 
-    None
+    // Trailing number without a unit
+    if !digits.is_empty() {
+        return None;
+    }
+
+    Some(total)
 }
 
-pub fn format_deadline(deadline: DateTime<Local>, is_overdue: bool) -> String {
-    let now = Local::now();
+/// Format a duration in minutes as `45m`, `2h` or `1h30m`.
+pub fn format_duration(minutes: u32) -> String {
+    let (hours, mins) = (minutes / 60, minutes % 60);
+    match (hours, mins) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h{}m", h, m),
+    }
+}
+
+pub fn format_deadline(deadline: Deadline, is_overdue: bool) -> String {
+    let all_day = deadline.is_all_day();
+    let deadline = deadline.in_viewer_zone();
+    let now = viewer_now();
     let today = now.date_naive();
     let deadline_date = deadline.date_naive();
-    let time_str = deadline.format("%-I:%M%P").to_string();
+    let time_str = if all_day {
+        String::new()
+    } else {
+        format!(" {}", deadline.format(clock_format()))
+    };
 
-    if is_overdue {
+    if is_overdue && all_day {
+        match (today - deadline_date).num_days() {
+            1 => "⚠ yesterday".to_string(),
+            days => format!("⚠ {}d ago", days),
+        }
+    } else if is_overdue {
         let diff = now - deadline;
         if diff.num_hours() < 1 {
             format!("⚠ {}m ago", diff.num_minutes())
@@ -197,15 +1174,47 @@ pub fn format_deadline(deadline: DateTime<Local>, is_overdue: bool) -> String {
             format!("⚠ {}d ago", diff.num_days())
         }
     } else if deadline_date == today {
-        format!("today {}", time_str)
+        format!("today{}", time_str)
     } else if deadline_date == today.succ_opt().unwrap_or(today) {
-        format!("tomorrow {}", time_str)
+        format!("tomorrow{}", time_str)
     } else if (deadline_date - today).num_days() < 7 {
-        deadline.format("%a %-I:%M%P").to_string().to_lowercase()
+        format!("{}{}", deadline.format("%a"), time_str).to_lowercase()
     } else if deadline.year() == now.year() {
-        deadline.format("%b %-d").to_string().to_lowercase()
+        deadline.format(month_day_format()).to_string().to_lowercase()
     } else {
-        deadline.format("%Y-%m-%d").to_string()
+        deadline.format(date_format()).to_string()
+    }
+}
+
+/// `3:30pm` or `15:30`, per the `clock` setting.
+pub fn clock_format() -> &'static str {
+    match options().clock {
+        ClockStyle::TwelveHour => "%-I:%M%P",
+        ClockStyle::TwentyFourHour => "%H:%M",
+    }
+}
+
+/// `12/25/2026`, `25/12/2026` or `2026-12-25`, per the `date_order` setting.
+pub fn date_format() -> &'static str {
+    match options().date_order {
+        DateOrder::Mdy => "%m/%d/%Y",
+        DateOrder::Dmy => "%d/%m/%Y",
+        DateOrder::Ymd => "%Y-%m-%d",
+    }
+}
+
+/// `Friday, December 25, 2026` or `Friday, 25 December 2026`.
+pub fn long_date_format() -> &'static str {
+    match options().date_order {
+        DateOrder::Mdy => "%A, %B %-d, %Y",
+        DateOrder::Dmy | DateOrder::Ymd => "%A, %-d %B %Y",
+    }
+}
+
+fn month_day_format() -> &'static str {
+    match options().date_order {
+        DateOrder::Dmy => "%-d %b",
+        DateOrder::Mdy | DateOrder::Ymd => "%b %-d",
     }
 }
 
@@ -224,12 +1233,403 @@ pub fn format_completed_time(completed: DateTime<Local>) -> String {
     }
 }
 
-pub fn is_due_today(deadline: DateTime<Local>) -> bool {
-    deadline.date_naive() == Local::now().date_naive()
+/// Compact age of a timestamp: `now`, `12m`, `5h`, `3d`.
+pub fn format_age(since: DateTime<Local>) -> String {
+    let diff = Local::now() - since;
+
+    if diff.num_minutes() < 1 {
+        "now".to_string()
+    } else if diff.num_hours() < 1 {
+        format!("{}m", diff.num_minutes())
+    } else if diff.num_days() < 1 {
+        format!("{}h", diff.num_hours())
+    } else {
+        format!("{}d", diff.num_days())
+    }
+}
+
+pub fn is_due_today(deadline: Deadline) -> bool {
+    deadline.in_viewer_zone().date_naive() == viewer_now().date_naive()
 }
 
-pub fn is_due_this_week(deadline: DateTime<Local>) -> bool {
-    let now = Local::now();
-    let diff = deadline.date_naive() - now.date_naive();
-    diff.num_days() >= 0 && diff.num_days() <= 7
+pub fn is_due_this_week(deadline: Deadline) -> bool {
+    let due = deadline.in_viewer_zone().date_naive();
+    let today = viewer_now().date_naive();
+    let options = options();
+
+    // A week of working days, so weekends and holidays don't shorten it
+    let last = if options.week == WeekMode::Calendar {
+        Some(start_of_week(today, options.week_start) + Duration::days(6))
+    } else if options.business_week {
+        options.calendar.add_workdays(today, options.calendar.week_length())
+    } else {
+        today.checked_add_signed(Duration::days(7))
+    };
+    due >= today && last.is_some_and(|last| due <= last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// Wednesday, March 11 2026, 10:00.
+    fn now() -> DateTime<Local> {
+        at(2026, 3, 11, 10, 0)
+    }
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(year, month, day, hour, minute, 0).single().unwrap()
+    }
+
+    fn parse_local(input: &str, now: DateTime<Local>, resolution: Resolution) -> Option<DateTime<Local>> {
+        parse_in(input, now, resolution).map(|(time, _)| time)
+    }
+
+    fn parse(input: &str) -> DateTime<Local> {
+        parse_local(input, now(), Resolution::Future).unwrap_or_else(|| panic!("could not parse {:?}", input))
+    }
+
+    #[test]
+    fn named_days() {
+        assert_eq!(parse("now"), now());
+        assert_eq!(parse("today"), at(2026, 3, 11, 23, 59));
+        assert_eq!(parse("eod"), at(2026, 3, 11, 23, 59));
+        assert_eq!(parse("tonight"), at(2026, 3, 11, 20, 0));
+        assert_eq!(parse("tomorrow"), at(2026, 3, 12, 9, 0));
+        assert_eq!(parse("yesterday"), at(2026, 3, 10, 9, 0));
+    }
+
+    #[test]
+    fn weekdays() {
+        assert_eq!(parse("fri"), at(2026, 3, 13, 9, 0));
+        assert_eq!(parse("Friday"), at(2026, 3, 13, 9, 0));
+        // A bare weekday includes today
+        assert_eq!(parse("wednesday"), at(2026, 3, 11, 9, 0));
+        assert_eq!(parse("wednesday 3pm"), at(2026, 3, 11, 15, 0));
+        assert_eq!(parse("this friday"), at(2026, 3, 13, 9, 0));
+        assert_eq!(parse("this wednesday"), at(2026, 3, 11, 9, 0));
+        assert_eq!(parse("last monday"), at(2026, 3, 9, 9, 0));
+        assert_eq!(parse("fri 3pm"), at(2026, 3, 13, 15, 0));
+    }
+
+    #[test]
+    fn next_weekday_is_in_next_week() {
+        assert_eq!(parse("next friday"), at(2026, 3, 20, 9, 0));
+        assert_eq!(parse("next monday"), at(2026, 3, 16, 9, 0));
+    }
+
+    #[test]
+    fn end_of_period() {
+        assert_eq!(parse("end of month"), at(2026, 3, 31, 23, 59));
+        assert_eq!(parse("end of the month"), at(2026, 3, 31, 23, 59));
+        assert_eq!(parse("eom"), at(2026, 3, 31, 23, 59));
+        assert_eq!(parse("eow"), at(2026, 3, 15, 23, 59));
+        assert_eq!(parse("end of next week"), at(2026, 3, 22, 23, 59));
+        assert_eq!(parse("eoy"), at(2026, 12, 31, 23, 59));
+    }
+
+    #[test]
+    fn start_of_period() {
+        assert_eq!(parse("next week"), at(2026, 3, 16, 9, 0));
+        assert_eq!(parse("next month"), at(2026, 4, 1, 9, 0));
+        assert_eq!(parse("next year"), at(2027, 1, 1, 9, 0));
+    }
+
+    #[test]
+    fn relative_offsets() {
+        assert_eq!(parse("in 2 hours"), now() + Duration::hours(2));
+        assert_eq!(parse("in 30 min"), now() + Duration::minutes(30));
+        assert_eq!(parse("in a week"), now() + Duration::weeks(1));
+        assert_eq!(parse("in 2 weeks 3 days"), now() + Duration::days(17));
+        assert_eq!(parse("in 2 weeks and 3 days"), now() + Duration::days(17));
+        assert_eq!(parse("in 1h30m"), now() + Duration::minutes(90));
+        assert_eq!(parse("in 2 months"), at(2026, 5, 11, 10, 0));
+        assert_eq!(parse("2 weeks from now"), now() + Duration::weeks(2));
+        assert_eq!(parse("in 2 days at 5pm"), at(2026, 3, 13, 17, 0));
+    }
+
+    #[test]
+    fn oversized_offsets_fail() {
+        for input in [
+            "in 99999999999999999 minutes",
+            "in 99999999999999 minutes",
+            "in 9999999999999 hours",
+            "in 99999999999 days",
+            "in 9999999999 years",
+            "99999999999999 minutes ago",
+        ] {
+            assert_eq!(parse_local(input, now(), Resolution::Future), None, "{}", input);
+        }
+    }
+
+    #[test]
+    fn past_offsets() {
+        assert_eq!(parse("3 days ago"), now() - Duration::days(3));
+        assert_eq!(parse("an hour ago"), now() - Duration::hours(1));
+    }
+
+    #[test]
+    fn times_of_day() {
+        assert_eq!(parse("11am"), at(2026, 3, 11, 11, 0));
+        assert_eq!(parse("3:30pm"), at(2026, 3, 11, 15, 30));
+        assert_eq!(parse("3 pm"), at(2026, 3, 11, 15, 0));
+        assert_eq!(parse("12am"), at(2026, 3, 12, 0, 0));
+        assert_eq!(parse("14:00"), at(2026, 3, 11, 14, 0));
+        assert_eq!(parse("at 15"), at(2026, 3, 11, 15, 0));
+        assert_eq!(parse("noon"), at(2026, 3, 11, 12, 0));
+        assert_eq!(parse("midnight"), at(2026, 3, 12, 0, 0));
+    }
+
+    #[test]
+    fn date_and_time_in_either_order() {
+        assert_eq!(parse("tomorrow 3pm"), at(2026, 3, 12, 15, 0));
+        assert_eq!(parse("tomorrow at 3pm"), at(2026, 3, 12, 15, 0));
+        assert_eq!(parse("3pm tomorrow"), at(2026, 3, 12, 15, 0));
+        assert_eq!(parse("tomorrow noon"), at(2026, 3, 12, 12, 0));
+    }
+
+    #[test]
+    fn numeric_dates() {
+        assert_eq!(parse("12/25"), at(2026, 12, 25, 9, 0));
+        assert_eq!(parse("12-25"), at(2026, 12, 25, 9, 0));
+        assert_eq!(parse("12/25 3pm"), at(2026, 12, 25, 15, 0));
+        assert_eq!(parse("12/25/2027"), at(2027, 12, 25, 9, 0));
+        assert_eq!(parse("12/25/27"), at(2027, 12, 25, 9, 0));
+        assert_eq!(parse("2024-12-25"), at(2024, 12, 25, 9, 0));
+        assert_eq!(parse("2024-12-25 14:00"), at(2024, 12, 25, 14, 0));
+    }
+
+    #[test]
+    fn month_names() {
+        assert_eq!(parse("dec 25"), at(2026, 12, 25, 9, 0));
+        assert_eq!(parse("december 25th"), at(2026, 12, 25, 9, 0));
+        assert_eq!(parse("25 dec"), at(2026, 12, 25, 9, 0));
+        assert_eq!(parse("may 5, 2027 9:30am"), at(2027, 5, 5, 9, 30));
+    }
+
+    #[test]
+    fn ordinal_weekdays() {
+        assert_eq!(parse("first monday of next month"), at(2026, 4, 6, 9, 0));
+        assert_eq!(parse("last friday of the month"), at(2026, 3, 27, 9, 0));
+        assert_eq!(parse("2nd tuesday of may"), at(2026, 5, 12, 9, 0));
+        assert_eq!(parse("first monday of next month 10am"), at(2026, 4, 6, 10, 0));
+        // April 2026 has only four Mondays
+        assert_eq!(parse_local("fifth monday of next month", now(), Resolution::Future), None);
+    }
+
+    fn resolve(input: &str, now: DateTime<Local>, resolution: Resolution) -> DateTime<Local> {
+        parse_local(input, now, resolution).unwrap_or_else(|| panic!("could not parse {:?}", input))
+    }
+
+    #[test]
+    fn future_resolution_skips_past_readings() {
+        let december = at(2026, 12, 20, 10, 0);
+        assert_eq!(resolve("1/5", december, Resolution::Future), at(2027, 1, 5, 9, 0));
+        assert_eq!(resolve("jan 5", december, Resolution::Future), at(2027, 1, 5, 9, 0));
+        assert_eq!(resolve("12/20", december, Resolution::Future), at(2026, 12, 20, 9, 0));
+        assert_eq!(resolve("9am", now(), Resolution::Future), at(2026, 3, 12, 9, 0));
+        assert_eq!(resolve("11am", now(), Resolution::Future), at(2026, 3, 11, 11, 0));
+        assert_eq!(resolve("midnight", now(), Resolution::Future), at(2026, 3, 12, 0, 0));
+        // Explicit years are never moved
+        assert_eq!(resolve("1/5/2026", december, Resolution::Future), at(2026, 1, 5, 9, 0));
+    }
+
+    #[test]
+    fn nearest_resolution_picks_closest_reading() {
+        let december = at(2026, 12, 20, 10, 0);
+        assert_eq!(resolve("1/5", december, Resolution::Nearest), at(2027, 1, 5, 9, 0));
+        assert_eq!(resolve("12/1", december, Resolution::Nearest), at(2026, 12, 1, 9, 0));
+        assert_eq!(resolve("9am", now(), Resolution::Nearest), at(2026, 3, 11, 9, 0));
+        assert_eq!(resolve("monday", now(), Resolution::Nearest), at(2026, 3, 9, 9, 0));
+        assert_eq!(resolve("friday", now(), Resolution::Nearest), at(2026, 3, 13, 9, 0));
+    }
+
+    #[test]
+    fn strict_resolution_takes_input_literally() {
+        let december = at(2026, 12, 20, 10, 0);
+        assert_eq!(resolve("1/5", december, Resolution::Strict), at(2026, 1, 5, 9, 0));
+        assert_eq!(resolve("9am", now(), Resolution::Strict), at(2026, 3, 11, 9, 0));
+        assert_eq!(resolve("wednesday", now(), Resolution::Strict), at(2026, 3, 11, 9, 0));
+    }
+
+    #[test]
+    fn past_resolution_looks_back() {
+        let december = at(2026, 12, 15, 10, 0);
+        assert_eq!(resolve("1/5", december, Resolution::Past), at(2026, 1, 5, 9, 0));
+        assert_eq!(resolve("12/20", december, Resolution::Past), at(2025, 12, 20, 9, 0));
+        assert_eq!(resolve("monday", now(), Resolution::Past), at(2026, 3, 9, 9, 0));
+        assert_eq!(resolve("friday", now(), Resolution::Past), at(2026, 3, 6, 9, 0));
+        assert_eq!(resolve("wednesday", now(), Resolution::Past), at(2026, 3, 11, 9, 0));
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        for input in ["", "blah", "in", "friday call bob", "13pm", "2/30", "25:00", "in 3 bananas"] {
+            assert_eq!(parse_local(input, now(), Resolution::Future), None, "{:?} should not parse", input);
+        }
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("1h30m"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("2d"), Some(Duration::days(2)));
+        assert_eq!(parse_duration("90"), None);
+        assert_eq!(parse_duration("99999999999999w"), None);
+        assert_eq!(parse_duration("9999999999999999999m"), None);
+        assert_eq!(format_duration(90), "1h30m");
+    }
+
+    fn deadline(input: &str) -> Deadline {
+        parse_deadline_with(input, now().with_timezone(&Utc), Resolution::Future)
+            .unwrap_or_else(|| panic!("could not parse {:?}", input))
+    }
+
+    #[test]
+    fn named_zones() {
+        let new_york: Tz = "America/New_York".parse().unwrap();
+        assert_eq!(
+            deadline("2026-03-13 3pm America/New_York"),
+            Deadline::At {
+                time: Utc.with_ymd_and_hms(2026, 3, 13, 19, 0, 0).unwrap(),
+                zone: Some(new_york),
+            }
+        );
+        assert_eq!(deadline("2026-03-13 3pm utc").zone(), Some(Tz::UTC));
+        assert_eq!(parse_deadline_with("3pm Mars/Olympus", now().with_timezone(&Utc), Resolution::Future), None);
+    }
+
+    #[test]
+    fn dst_transitions() {
+        let new_york: Tz = "America/New_York".parse().unwrap();
+        let wall = |m, d, h, min| NaiveDate::from_ymd_opt(2026, m, d).unwrap().and_hms_opt(h, min, 0).unwrap();
+        let utc = |m, d, h, min| Utc.with_ymd_and_hms(2026, m, d, h, min, 0).unwrap();
+
+        // 2:30 doesn't exist on March 8; it moves forward to 3:30 EDT
+        let skipped = localize(&new_york, wall(3, 8, 2, 30)).unwrap();
+        assert_eq!(skipped.with_timezone(&Utc), utc(3, 8, 7, 30));
+        assert_eq!(skipped.naive_local(), wall(3, 8, 3, 30));
+
+        // 1:30 happens twice on November 1; the first (EDT) one wins
+        let repeated = localize(&new_york, wall(11, 1, 1, 30)).unwrap();
+        assert_eq!(repeated.with_timezone(&Utc), utc(11, 1, 5, 30));
+
+        // Either side of the changes is unaffected
+        assert_eq!(localize(&new_york, wall(3, 8, 1, 30)).unwrap().with_timezone(&Utc), utc(3, 8, 6, 30));
+        assert_eq!(localize(&new_york, wall(11, 1, 2, 30)).unwrap().with_timezone(&Utc), utc(11, 1, 7, 30));
+
+        // The same rules apply to a zone named in the input
+        assert_eq!(deadline("2026-03-08 2:30am America/New_York").instant(), utc(3, 8, 7, 30));
+        assert_eq!(deadline("2026-11-01 1:30am America/New_York").instant(), utc(11, 1, 5, 30));
+    }
+
+    #[test]
+    fn floating_deadlines() {
+        let friday = NaiveDate::from_ymd_opt(2026, 3, 13).unwrap();
+        assert_eq!(deadline("friday 3pm floating"), Deadline::Floating(friday.and_hms_opt(15, 0, 0).unwrap()));
+        assert!(matches!(deadline("friday 3pm"), Deadline::At { zone: None, .. }));
+    }
+
+    #[test]
+    fn all_day_deadlines() {
+        let day = |d| Deadline::Day(NaiveDate::from_ymd_opt(2026, 3, d).unwrap());
+        assert_eq!(deadline("today"), day(11));
+        assert_eq!(deadline("friday"), day(13));
+        assert_eq!(deadline("3/13"), day(13));
+        assert_eq!(deadline("eow"), day(15));
+        assert_eq!(deadline("friday Asia/Tokyo"), day(13));
+        assert!(!deadline("friday 9am").is_all_day());
+        assert!(!deadline("in 3 days").is_all_day());
+
+        // Due until the day is over, and ahead of timed tasks that day
+        assert!(day(11).instant() > now());
+        assert!(day(13).sort_key() < deadline("friday 9am").sort_key());
+        assert!(day(13).sort_key() > deadline("thursday 11pm").sort_key());
+    }
+
+    #[test]
+    fn deadline_round_trip() {
+        for input in ["2026-03-13 3pm America/New_York", "friday", "friday 3pm floating", "in 2 hours"] {
+            let parsed = deadline(input);
+            assert_eq!(parsed.to_string().parse::<Deadline>(), Ok(parsed));
+        }
+        let legacy: Deadline = "2026-03-13T15:00:00-04:00".parse().unwrap();
+        assert_eq!(legacy, Deadline::at(Utc.with_ymd_and_hms(2026, 3, 13, 19, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn business_days() {
+        // Wednesday 10:00: three working days later is Monday
+        assert_eq!(parse("in 3 business days"), at(2026, 3, 16, 10, 0));
+        assert_eq!(parse("in 2 working days at 5pm"), at(2026, 3, 13, 17, 0));
+        assert_eq!(parse("3 workdays ago"), at(2026, 3, 6, 10, 0));
+        assert_eq!(parse("next workday"), at(2026, 3, 12, 9, 0));
+        assert_eq!(parse("last business day"), at(2026, 3, 10, 9, 0));
+
+        let friday = NaiveDate::from_ymd_opt(2026, 3, 13).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2026, 3, 16).unwrap();
+        let calendar = WorkCalendar::new(vec![Weekday::Sun, Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu], BTreeSet::from([monday]));
+        assert!(!calendar.is_workday(friday));
+        assert_eq!(calendar.add_workdays(friday, 1), NaiveDate::from_ymd_opt(2026, 3, 15));
+        assert_eq!(calendar.add_workdays(friday, 2), NaiveDate::from_ymd_opt(2026, 3, 17));
+        assert_eq!(calendar.add_workdays(monday, -1), NaiveDate::from_ymd_opt(2026, 3, 15));
+    }
+
+    #[test]
+    fn holiday_files() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        let list = parse_holidays("# public holidays\n2026-12-25 Christmas\n\n2026-12-26\n").unwrap();
+        assert_eq!(list, BTreeSet::from([date(12, 25), date(12, 26)]));
+        assert!(parse_holidays("2026-13-01").is_err());
+
+        let ics = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART;VALUE=DATE:20261224\nDTEND;VALUE=DATE:20261227\n\
+                   SUMMARY:Christmas\nEND:VEVENT\nBEGIN:VEVENT\nDTSTART:20261231T090000Z\nEND:VEVENT\nEND:VCALENDAR\n";
+        assert_eq!(parse_holidays(ics).unwrap(), BTreeSet::from([date(12, 24), date(12, 25), date(12, 26), date(12, 31)]));
+    }
+
+    #[test]
+    fn date_orders() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        assert_eq!(parse_numeric_date("05/06", 2026, DateOrder::Mdy), Some((date(5, 6), false)));
+        assert_eq!(parse_numeric_date("05/06", 2026, DateOrder::Dmy), Some((date(6, 5), false)));
+        assert_eq!(parse_numeric_date("05/06", 2026, DateOrder::Ymd), Some((date(5, 6), false)));
+        assert_eq!(parse_numeric_date("25/12/2026", 2026, DateOrder::Dmy), Some((date(12, 25), true)));
+        assert_eq!(parse_numeric_date("26/12/25", 2026, DateOrder::Ymd), Some((date(12, 25), true)));
+        assert_eq!(parse_numeric_date("2026-12-25", 2026, DateOrder::Dmy), Some((date(12, 25), true)));
+        assert_eq!(parse_numeric_date("25/12", 2026, DateOrder::Mdy), None);
+    }
+
+    #[test]
+    fn week_starts() {
+        let wednesday = NaiveDate::from_ymd_opt(2026, 3, 11).unwrap();
+        assert_eq!(start_of_week(wednesday, WeekStart::Monday), NaiveDate::from_ymd_opt(2026, 3, 9).unwrap());
+        assert_eq!(start_of_week(wednesday, WeekStart::Sunday), NaiveDate::from_ymd_opt(2026, 3, 8).unwrap());
+        let sunday = NaiveDate::from_ymd_opt(2026, 3, 15).unwrap();
+        assert_eq!(start_of_week(sunday, WeekStart::Sunday), sunday);
+    }
+
+    #[test]
+    fn shifting_deadlines() {
+        let day = |d| Deadline::Day(NaiveDate::from_ymd_opt(2026, 3, d).unwrap());
+        assert_eq!(shift_deadline(day(13), "+3d"), Some(day(16)));
+        assert_eq!(shift_deadline(day(13), "-1 week"), Some(day(6)));
+        assert_eq!(shift_deadline(day(13), "1 business day"), Some(day(16)));
+        assert_eq!(shift_deadline(day(13), "+soon"), None);
+
+        let new_york: Tz = "America/New_York".parse().unwrap();
+        let call = deadline("2026-03-06 3pm America/New_York");
+        // Across the DST change the wall time stays at 3pm in New York
+        assert_eq!(shift_deadline(call, "1w"), Some(deadline("2026-03-13 3pm America/New_York")));
+        assert_eq!(shift_deadline(call, "1w").and_then(Deadline::zone), Some(new_york));
+    }
+
+    #[test]
+    fn configured_times() {
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0);
+        assert_eq!(parse_time_of_day("9:00"), time(9, 0));
+        assert_eq!(parse_time_of_day("17:30"), time(17, 30));
+        assert_eq!(parse_time_of_day("5PM"), time(17, 0));
+        assert_eq!(parse_time_of_day("9"), None);
+        assert_eq!(parse_time_of_day("noon"), None);
+    }
 }
@@ -1,40 +1,101 @@
-use chrono::{DateTime, Local};
+use crate::deadline::Deadline;
+use crate::reminder::Reminder;
+use chrono::{DateTime, Local, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Todo {
+    /// Short handle, unique within this store
     pub id: u32,
+    /// Globally unique ID; assigned on load for tasks saved before it existed
+    #[serde(default)]
+    pub uuid: String,
     pub text: String,
     pub done: bool,
     pub priority: Option<u8>,
-    pub deadline: Option<DateTime<Local>>,
+    pub deadline: Option<Deadline>,
     pub tags: Vec<String>,
     pub project: Option<String>,
+    /// Estimated effort in minutes
+    #[serde(default)]
+    pub estimate: Option<u32>,
+    #[serde(default)]
+    pub reminders: Vec<Reminder>,
     pub created_at: DateTime<Local>,
     pub completed_at: Option<DateTime<Local>>,
+    /// Field changes since the task was created, oldest first
+    #[serde(default)]
+    pub history: Vec<Change>,
+}
+
+/// A task field tracked in the history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Field {
+    Text,
+    Status,
+    Priority,
+    Deadline,
+    Project,
+    Tags,
+    Estimate,
+}
+
+impl Field {
+    pub fn label(self) -> &'static str {
+        match self {
+            Field::Text => "text",
+            Field::Status => "status",
+            Field::Priority => "priority",
+            Field::Deadline => "deadline",
+            Field::Project => "project",
+            Field::Tags => "tags",
+            Field::Estimate => "estimate",
+        }
+    }
+}
+
+/// One change to a task. Values are in their stored form: deadlines as
+/// written to the store, tags space-separated, None when unset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Change {
+    pub at: DateTime<Local>,
+    pub field: Field,
+    pub from: Option<String>,
+    pub to: Option<String>,
 }
 
 impl Todo {
     pub fn new(id: u32, text: String) -> Self {
         Self {
             id,
+            uuid: Uuid::new_v4().to_string(),
             text,
             done: false,
             priority: None,
             deadline: None,
             tags: Vec::new(),
             project: None,
+            estimate: None,
+            reminders: Vec::new(),
             created_at: Local::now(),
             completed_at: None,
+            history: Vec::new(),
         }
     }
 
+    /// First eight characters of the UUID, enough to identify a task.
+    pub fn short_uuid(&self) -> &str {
+        &self.uuid[..self.uuid.len().min(8)]
+    }
+
     pub fn with_priority(mut self, priority: Option<u8>) -> Self {
         self.priority = priority;
         self
     }
 
-    pub fn with_deadline(mut self, deadline: Option<DateTime<Local>>) -> Self {
+    pub fn with_deadline(mut self, deadline: Option<Deadline>) -> Self {
         self.deadline = deadline;
         self
     }
@@ -49,16 +110,63 @@ impl Todo {
         self
     }
 
+    pub fn with_estimate(mut self, estimate: Option<u32>) -> Self {
+        self.estimate = estimate;
+        self
+    }
+
+    pub fn with_reminders(mut self, reminders: Vec<Reminder>) -> Self {
+        self.reminders = reminders;
+        self
+    }
+
+    /// True if the task is in `project` or one of its dotted subprojects,
+    /// so `client.acme` matches `client.acme.website`.
     pub fn in_project(&self, project: &str) -> bool {
         self.project
             .as_ref()
-            .map(|p| p.eq_ignore_ascii_case(project))
+            .map(|p| is_subproject(p, project))
             .unwrap_or(false)
     }
 
     pub fn mark_done(&mut self) {
         self.done = true;
         self.completed_at = Some(Local::now());
+        self.log(Field::Status, Some("open".to_string()), Some("done".to_string()));
+    }
+
+    pub fn reopen(&mut self) {
+        self.done = false;
+        self.completed_at = None;
+        self.log(Field::Status, Some("done".to_string()), Some("open".to_string()));
+    }
+
+    /// Record how this task differs from `before`, an earlier copy of it.
+    pub fn record_changes(&mut self, before: &Todo) {
+        let tags = |todo: &Todo| (!todo.tags.is_empty()).then(|| todo.tags.join(" "));
+        let fields = [
+            (Field::Text, Some(before.text.clone()), Some(self.text.clone())),
+            (Field::Priority, before.priority.map(|p| p.to_string()), self.priority.map(|p| p.to_string())),
+            (Field::Deadline, before.deadline.map(|d| d.to_string()), self.deadline.map(|d| d.to_string())),
+            (Field::Project, before.project.clone(), self.project.clone()),
+            (Field::Tags, tags(before), tags(self)),
+            (Field::Estimate, before.estimate.map(|e| e.to_string()), self.estimate.map(|e| e.to_string())),
+        ];
+
+        for (field, from, to) in fields {
+            if from != to {
+                self.log(field, from, to);
+            }
+        }
+    }
+
+    fn log(&mut self, field: Field, from: Option<String>, to: Option<String>) {
+        self.history.push(Change {
+            at: Local::now(),
+            field,
+            from,
+            to,
+        });
     }
 
     pub fn priority_display(&self) -> &'static str {
@@ -86,9 +194,20 @@ impl Todo {
 
     pub fn is_overdue(&self) -> bool {
         if let Some(deadline) = self.deadline {
-            !self.done && deadline < Local::now()
+            !self.done && deadline.instant() < Utc::now()
         } else {
             false
         }
     }
 }
+
+/// True if `name` equals `ancestor` or sits below it in the dotted project
+/// tree. Comparison ignores case.
+pub fn is_subproject(name: &str, ancestor: &str) -> bool {
+    match name.get(..ancestor.len()) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(ancestor) => {
+            name.len() == ancestor.len() || name[ancestor.len()..].starts_with('.')
+        }
+        _ => false,
+    }
+}